@@ -0,0 +1,181 @@
+//! Error types of the crate.
+
+use fieldx::error::FieldXError;
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::Display;
+
+/// The role of the object referred to by an agent or a child.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Application object of an agent.
+    App,
+    /// Parent object of a child.
+    Parent,
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::App => "application",
+            Self::Parent => "parent",
+        })
+    }
+}
+
+/// Reported by `app()`/`parent()` methods declared with `unwrap(error)` when the app/parent object has been dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParentGone {
+    child_type:  &'static str,
+    parent_type: &'static str,
+    role:        Role,
+}
+
+impl ParentGone {
+    /// Create an error for child type `C` that lost its parent of type `P`. Use [`for_role`](Self::for_role) to
+    /// report a lost application.
+    pub fn new<C: ?Sized, P: ?Sized>() -> Self {
+        Self::for_role::<C, P>(Role::Parent)
+    }
+
+    /// Create an error for child type `C` that lost its app/parent of type `P`.
+    pub fn for_role<C: ?Sized, P: ?Sized>(role: Role) -> Self {
+        Self {
+            child_type: type_name::<C>(),
+            parent_type: type_name::<P>(),
+            role,
+        }
+    }
+
+    /// Type name of the agent/child.
+    pub fn child_type(&self) -> &'static str {
+        self.child_type
+    }
+
+    /// Type name of the app/parent.
+    pub fn parent_type(&self) -> &'static str {
+        self.parent_type
+    }
+
+    /// If the lost object is an app or a parent.
+    pub fn role(&self) -> Role {
+        self.role
+    }
+}
+
+impl Display for ParentGone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} object `{}` of `{}` is gone",
+            self.role, self.parent_type, self.child_type
+        )
+    }
+}
+
+impl Error for ParentGone {}
+
+/// Reported by [`children_build!`](crate::children_build!) and [`agents_build!`](crate::agents_build!) when one of
+/// the builds fails. Carries the index of the failed item and the builder error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchBuildError<E> {
+    index:  usize,
+    source: E,
+}
+
+impl<E> BatchBuildError<E> {
+    /// Create an error for the item at `index`.
+    pub fn new(index: usize, source: E) -> Self {
+        Self { index, source }
+    }
+
+    /// Index of the item that failed to build.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The builder error. Named so to not shadow [`Error::source`].
+    pub fn error(&self) -> &E {
+        &self.source
+    }
+
+    /// Consume the error and return the builder error.
+    pub fn into_source(self) -> E {
+        self.source
+    }
+}
+
+impl<E: Display> Display for BatchBuildError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to build item #{}: {}", self.index, self.source)
+    }
+}
+
+impl<E: Error + 'static> Error for BatchBuildError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Error type of the `build()` method of agent and child builders declared with `build_error` or `check_alive`.
+#[derive(Debug, Clone)]
+pub enum BuildError {
+    /// The app/parent has not been set with the builder.
+    ParentNotSet {
+        /// If it is the app or the parent that is missing.
+        role:       Role,
+        /// Type name of the agent/child.
+        child_type: &'static str,
+    },
+    /// The app/parent is gone by the time the agent/child is built. Only reported for relationships declared with
+    /// `check_alive`.
+    ParentGoneAtBuild(ParentGone),
+    /// Any other builder error, like a required field which is not set.
+    Field(FieldXError),
+}
+
+impl BuildError {
+    /// Create an error for child type `C` reporting that its app/parent is not set.
+    pub fn parent_not_set<C: ?Sized>(role: Role) -> Self {
+        Self::ParentNotSet {
+            role,
+            child_type: type_name::<C>(),
+        }
+    }
+
+    // Used by the builders to report a field that is not set. `parents` maps the names of app/parent fields to their
+    // roles.
+    #[doc(hidden)]
+    pub fn __field_not_set<C: ?Sized>(field: String, parents: &[(&str, Role)]) -> Self {
+        match parents.iter().find(|(name, _)| *name == field) {
+            Some((_, role)) => Self::parent_not_set::<C>(*role),
+            None => Self::Field(FieldXError::uninitialized_field(field)),
+        }
+    }
+}
+
+impl From<FieldXError> for BuildError {
+    fn from(err: FieldXError) -> Self {
+        Self::Field(err)
+    }
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParentNotSet { role, child_type } => write!(f, "{role} of `{child_type}` is not set"),
+            Self::ParentGoneAtBuild(gone) => write!(f, "cannot build: {gone}"),
+            Self::Field(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for BuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ParentNotSet { .. } => None,
+            Self::ParentGoneAtBuild(gone) => Some(gone),
+            Self::Field(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Typed weak handles to applications and parents.
+//!
+//! Depending on the struct mode an agent or a child refers to its app/parent with either [`std::rc::Weak`] or
+//! [`std::sync::Weak`]. [`AppRef`] and [`ParentRef`] wrap either of them and provide the same interface regardless of
+//! the mode, which simplifies writing code that is generic over it.
+
+use crate::traits::AppSource;
+use std::any::type_name;
+use std::fmt::Debug;
+
+/// Common interface of weak reference-counted pointers.
+pub trait WeakPointer: Clone {
+    /// The type the pointer refers to.
+    type Target: ?Sized;
+    /// Strong counterpart of the pointer.
+    type Strong;
+
+    /// Attempt to get a strong reference to the target.
+    fn upgrade(&self) -> Option<Self::Strong>;
+    /// `true` if both pointers refer to the same allocation.
+    fn ptr_eq(&self, other: &Self) -> bool;
+    /// Number of strong references to the target.
+    fn strong_count(&self) -> usize;
+    /// Create a weak pointer to the target of a strong reference.
+    fn downgrade(strong: &Self::Strong) -> Self;
+}
+
+impl<T: ?Sized> WeakPointer for std::rc::Weak<T> {
+    type Strong = std::rc::Rc<T>;
+    type Target = T;
+
+    #[inline(always)]
+    fn upgrade(&self) -> Option<Self::Strong> {
+        std::rc::Weak::upgrade(self)
+    }
+
+    #[inline(always)]
+    fn ptr_eq(&self, other: &Self) -> bool {
+        std::rc::Weak::ptr_eq(self, other)
+    }
+
+    #[inline(always)]
+    fn strong_count(&self) -> usize {
+        std::rc::Weak::strong_count(self)
+    }
+
+    #[inline(always)]
+    fn downgrade(strong: &Self::Strong) -> Self {
+        std::rc::Rc::downgrade(strong)
+    }
+}
+
+impl<T: ?Sized> WeakPointer for std::sync::Weak<T> {
+    type Strong = std::sync::Arc<T>;
+    type Target = T;
+
+    #[inline(always)]
+    fn upgrade(&self) -> Option<Self::Strong> {
+        std::sync::Weak::upgrade(self)
+    }
+
+    #[inline(always)]
+    fn ptr_eq(&self, other: &Self) -> bool {
+        std::sync::Weak::ptr_eq(self, other)
+    }
+
+    #[inline(always)]
+    fn strong_count(&self) -> usize {
+        std::sync::Weak::strong_count(self)
+    }
+
+    #[inline(always)]
+    fn downgrade(strong: &Self::Strong) -> Self {
+        std::sync::Arc::downgrade(strong)
+    }
+}
+
+macro_rules! weak_handle {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Clone)]
+        pub struct $name<W: WeakPointer> {
+            weak: W,
+        }
+
+        impl<W: WeakPointer> $name<W> {
+            /// Wrap a weak pointer.
+            #[inline(always)]
+            pub fn new(weak: W) -> Self {
+                Self { weak }
+            }
+
+            /// Attempt to get a strong reference to the object.
+            #[inline(always)]
+            pub fn upgrade(&self) -> Option<W::Strong> {
+                self.weak.upgrade()
+            }
+
+            /// `true` if both handles refer to the same object.
+            #[inline(always)]
+            pub fn ptr_eq(&self, other: &Self) -> bool {
+                self.weak.ptr_eq(&other.weak)
+            }
+
+            /// `true` if the object hasn't been dropped yet.
+            #[inline(always)]
+            pub fn is_alive(&self) -> bool {
+                self.weak.strong_count() > 0
+            }
+
+            /// Borrow the wrapped weak pointer.
+            #[inline(always)]
+            pub fn weak(&self) -> &W {
+                &self.weak
+            }
+
+            /// Unwrap the weak pointer.
+            #[inline(always)]
+            pub fn into_weak(self) -> W {
+                self.weak
+            }
+
+            // Let the handle be used as the first argument of the builder macros.
+            #[doc(hidden)]
+            #[inline(always)]
+            pub fn __fxplus_myself_downgrade(&self) -> W {
+                self.weak.clone()
+            }
+        }
+
+        impl<W: WeakPointer> WeakPointer for $name<W> {
+            type Strong = W::Strong;
+            type Target = W::Target;
+
+            #[inline(always)]
+            fn upgrade(&self) -> Option<Self::Strong> {
+                self.weak.upgrade()
+            }
+
+            #[inline(always)]
+            fn ptr_eq(&self, other: &Self) -> bool {
+                self.weak.ptr_eq(&other.weak)
+            }
+
+            #[inline(always)]
+            fn strong_count(&self) -> usize {
+                self.weak.strong_count()
+            }
+
+            #[inline(always)]
+            fn downgrade(strong: &Self::Strong) -> Self {
+                Self::new(W::downgrade(strong))
+            }
+        }
+
+        impl<W: WeakPointer> AppSource<W> for $name<W> {
+            #[inline(always)]
+            fn app_weak(&self) -> W {
+                self.weak.clone()
+            }
+        }
+
+        impl<W: WeakPointer> From<W> for $name<W> {
+            #[inline(always)]
+            fn from(weak: W) -> Self {
+                Self::new(weak)
+            }
+        }
+
+        impl<W: WeakPointer> Debug for $name<W> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(&format!("{}<{}>", stringify!($name), type_name::<W::Target>()))
+                    .field("alive", &self.is_alive())
+                    .finish()
+            }
+        }
+    };
+}
+
+weak_handle! {
+    /// A weak handle to an application object, as held by agents.
+    AppRef
+}
+
+weak_handle! {
+    /// A weak handle to a parent object, as held by children.
+    ParentRef
+}
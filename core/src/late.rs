@@ -0,0 +1,173 @@
+//! Late binding of agents and children to their app/parent.
+//!
+//! An agent declared with `agent(App, late_bind)` or a child declared with `child(Parent, late_bind)` is built without
+//! a reference to its app/parent. The reference is set later with the generated `bind_app(&app)` or
+//! `bind_parent(&parent)` method. Until then the `app()`/`parent()` methods behave as if the app/parent is gone.
+//!
+//! In `async` mode there is also `app_ready().await`/`parent_ready().await` which waits until the binding happens.
+//! It uses the notification primitive of the active async backend, i.e. it requires either `async-tokio` or
+//! `async-lock` feature. The method returns `None` if the app/parent has been dropped after the binding.
+//!
+//! Note that the waiting future borrows the agent/child and there is nothing that could cancel it: if `bind_app()` or
+//! `bind_parent()` is never called the future never resolves. When the binding may not happen, e.g. because the
+//! app/parent construction can fail, use a timeout or race the future against some shutdown signal.
+//!
+//! The builder macros ([`agent_build!`](crate::agent_build!), [`children_build!`](crate::children_build!), etc.) and
+//! the [`agent_builder`](crate::Application::agent_builder)/[`child_builder`](crate::Parent::child_builder) methods
+//! cannot set a late-bound app/parent because the builder has no place for it. Rather than silently dropping it, they
+//! refuse to compile for such structs:
+//!
+//! ```compile_fail
+//! use fieldx_plus::agent_build;
+//! use fieldx_plus::fx_plus;
+//!
+//! #[fx_plus(app)]
+//! struct App {}
+//!
+//! #[fx_plus(agent(App, late_bind))]
+//! struct EarlyAgent {}
+//!
+//! let app = App::new();
+//! let agent = agent_build!(app, EarlyAgent).unwrap();
+//! ```
+//!
+//! Build them with their own builder and bind the app/parent afterwards:
+//!
+//! ```
+//! # use fieldx_plus::fx_plus;
+//! # #[fx_plus(app)]
+//! # struct App {}
+//! # #[fx_plus(agent(App, late_bind))]
+//! # struct EarlyAgent {}
+//! let agent = EarlyAgent::builder().build().unwrap();
+//! let app = App::new();
+//! agent.bind_app(&app);
+//! ```
+
+use crate::WeakPointer;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+/// A weak reference to the app/parent that can be set after the agent/child is created.
+pub struct LateBind<W: WeakPointer + Default> {
+    weak:   Mutex<W>,
+    bound:  AtomicBool,
+    #[cfg(feature = "tokio-backend")]
+    notify: tokio::sync::Notify,
+    #[cfg(all(feature = "async-lock-backend", not(feature = "tokio-backend")))]
+    notify: event_listener::Event,
+}
+
+impl<W: WeakPointer + Default> LateBind<W> {
+    /// Create an unbound reference.
+    pub fn new() -> Self {
+        Self::with_weak(W::default(), false)
+    }
+
+    fn with_weak(weak: W, bound: bool) -> Self {
+        Self {
+            weak: Mutex::new(weak),
+            bound: AtomicBool::new(bound),
+            #[cfg(any(feature = "tokio-backend", feature = "async-lock-backend"))]
+            notify: Default::default(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, W> {
+        // Nothing can be left in inconsistent state by a panic while the lock is held.
+        self.weak.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Return a clone of the weak reference. It is an empty reference until the binding takes place.
+    pub fn get(&self) -> W {
+        self.lock().clone()
+    }
+
+    /// Set the weak reference and wake up the tasks waiting for it. Binding again replaces the reference.
+    pub fn bind(&self, weak: W) {
+        *self.lock() = weak;
+        self.bound.store(true, Ordering::Release);
+
+        #[cfg(feature = "tokio-backend")]
+        self.notify.notify_waiters();
+        #[cfg(all(feature = "async-lock-backend", not(feature = "tokio-backend")))]
+        self.notify.notify(usize::MAX);
+    }
+
+    /// `true` if the reference has been bound.
+    pub fn is_bound(&self) -> bool {
+        self.bound.load(Ordering::Acquire)
+    }
+
+    /// Wait until the reference is bound. Pending forever if [`bind`](Self::bind) is never called.
+    #[cfg(any(feature = "tokio-backend", feature = "async-lock-backend"))]
+    pub async fn wait(&self) {
+        loop {
+            // Subscribe before checking the flag to not miss a notification sent in between.
+            #[cfg(feature = "tokio-backend")]
+            let notified = self.notify.notified();
+            #[cfg(all(feature = "async-lock-backend", not(feature = "tokio-backend")))]
+            let notified = self.notify.listen();
+
+            if self.is_bound() {
+                return;
+            }
+
+            notified.await;
+        }
+    }
+}
+
+impl<W: WeakPointer + Default> Default for LateBind<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: WeakPointer + Default> Clone for LateBind<W> {
+    fn clone(&self) -> Self {
+        Self::with_weak(self.get(), self.is_bound())
+    }
+}
+
+impl<W: WeakPointer + Default> Debug for LateBind<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LateBind")
+            .field("bound", &self.is_bound())
+            .field("alive", &(self.lock().strong_count() > 0))
+            .finish()
+    }
+}
+
+/// What the builder macros get for a `late_bind` agent/child instead of its builder. It cannot be built, see the
+/// [module documentation](self).
+#[doc(hidden)]
+pub struct LateBindBuilder<T>(PhantomData<T>);
+
+impl<T> LateBindBuilder<T> {
+    /// Never compiles because no type implements [`NotLateBound`].
+    pub fn build(self) -> Result<T, std::convert::Infallible>
+    where
+        T: NotLateBound,
+    {
+        unreachable!()
+    }
+}
+
+impl<T> Default for LateBindBuilder<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Not implemented for any type, only reports the misuse of a late-bound agent/child.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is declared with `late_bind` and cannot be built with its app/parent set",
+    note = "build it with `{Self}::builder()` and set the app/parent with `bind_app()`/`bind_parent()`"
+)]
+pub trait NotLateBound {}
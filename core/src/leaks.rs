@@ -0,0 +1,225 @@
+//! Detection of application objects that are never dropped.
+//!
+//! A child declared with `rc_strong` holds an owning reference to its parent. If the parent also keeps the child, in
+//! a lazy field for example, the pair forms a reference cycle and is never freed. `fx_plus` warns about such fields at
+//! compile time with a deprecation warning:
+//!
+//! ```compile_fail
+//! #![deny(deprecated)]
+//! use fieldx_plus::child_build;
+//! use fieldx_plus::fx_plus;
+//!
+//! #[fx_plus(app)]
+//! struct Service {
+//!     #[fieldx(lazy)]
+//!     worker: Worker,
+//! }
+//!
+//! impl Service {
+//!     fn build_worker(&self) -> Worker {
+//!         child_build!(self, Worker).unwrap()
+//!     }
+//! }
+//!
+//! #[fx_plus(child(Service, rc_strong))]
+//! struct Worker {}
+//! # fn main() {}
+//! ```
+//!
+//! Cycles formed through containers or trait objects can only be detected at run time. Declare an application with
+//! `track_leaks`, as in `#[fx_plus(app, track_leaks)]`, and with `debug_assertions` enabled its objects are registered
+//! when created and unregistered when dropped. Create a [`LeakGuard`] at the beginning of `main` and it will pass the
+//! applications that are still alive by the time it is dropped to the given handler. In release builds the tracking is
+//! compiled out and the guard does nothing.
+//!
+//! ```
+//! use fieldx_plus::fx_plus;
+//! use fieldx_plus::leaks::LeakGuard;
+//!
+//! #[fx_plus(app, track_leaks)]
+//! struct Application {}
+//!
+//! fn main() {
+//!     let _leak_guard = LeakGuard::new(|leaked| assert!(leaked.is_empty(), "leaked applications: {leaked:?}"));
+//!     let app = Application::new();
+//!     // ... run the application ...
+//! }
+//! ```
+
+use std::any::type_name;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+#[cfg(debug_assertions)]
+mod registry {
+    use std::collections::BTreeMap;
+    use std::sync::atomic::AtomicU64;
+    use std::sync::atomic::Ordering;
+    use std::sync::Mutex;
+    use std::sync::MutexGuard;
+
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    static ALIVE: Mutex<BTreeMap<u64, &'static str>> = Mutex::new(BTreeMap::new());
+
+    fn alive() -> MutexGuard<'static, BTreeMap<u64, &'static str>> {
+        // A poisoned registry is still usable since it is never left in an inconsistent state.
+        ALIVE.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    pub(super) fn register(type_name: &'static str) -> u64 {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        alive().insert(id, type_name);
+        id
+    }
+
+    pub(super) fn unregister(id: u64) {
+        alive().remove(&id);
+    }
+
+    pub(super) fn alive_apps() -> Vec<&'static str> {
+        alive().values().copied().collect()
+    }
+}
+
+/// Return type names of application objects declared with `track_leaks` that are currently alive, in the order of
+/// their creation. Always empty when `debug_assertions` are off.
+pub fn alive_apps() -> Vec<&'static str> {
+    #[cfg(debug_assertions)]
+    {
+        registry::alive_apps()
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        Vec::new()
+    }
+}
+
+/// Reports application objects that are still alive when the guard is dropped. Normally this means they are a part of
+/// a strong reference cycle.
+#[derive(Debug)]
+pub struct LeakGuard {
+    on_leak: fn(&[&'static str]),
+}
+
+impl LeakGuard {
+    /// Create a guard that passes the type names of leaked applications to the handler.
+    pub fn new(on_leak: fn(&[&'static str])) -> Self {
+        Self { on_leak }
+    }
+}
+
+impl Drop for LeakGuard {
+    fn drop(&mut self) {
+        let leaked = alive_apps();
+        if !leaked.is_empty() {
+            (self.on_leak)(&leaked);
+        }
+    }
+}
+
+/// A field `fx_plus` adds to application structs declared with `track_leaks` to track their lifetime. It is a
+/// zero-sized type when `debug_assertions` are off.
+///
+/// All trackers are equal to each other so that they don't affect derived `PartialEq`, `Eq`, or `Hash`
+/// implementations.
+#[doc(hidden)]
+pub struct AppTracker {
+    #[cfg(debug_assertions)]
+    id:        u64,
+    #[cfg(debug_assertions)]
+    type_name: &'static str,
+}
+
+impl AppTracker {
+    pub fn new<T: ?Sized>() -> Self {
+        Self::with_type_name(type_name::<T>())
+    }
+
+    #[cfg(debug_assertions)]
+    fn with_type_name(type_name: &'static str) -> Self {
+        Self {
+            id: registry::register(type_name),
+            type_name,
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn with_type_name(_type_name: &'static str) -> Self {
+        Self {}
+    }
+}
+
+impl Clone for AppTracker {
+    // A clone of an application object is another application object which must be tracked on its own.
+    #[cfg(debug_assertions)]
+    fn clone(&self) -> Self {
+        Self::with_type_name(self.type_name)
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn clone(&self) -> Self {
+        Self {}
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Drop for AppTracker {
+    fn drop(&mut self) {
+        registry::unregister(self.id);
+    }
+}
+
+impl Debug for AppTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AppTracker")
+    }
+}
+
+impl PartialEq for AppTracker {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for AppTracker {}
+
+impl Hash for AppTracker {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use std::marker::PhantomData;
+
+    /// Implemented by agents and children declared with `rc_strong` for their app/parent type `P`.
+    pub trait RcStrongTo<P: ?Sized> {}
+
+    // Autoref-based specialization: if `T` holds a strong reference to `P` then the deprecated method of
+    // `StrongCycleProbe` is picked without autoref and the compiler emits a warning. Otherwise the method resolution
+    // falls back to `NoCycleProbe` implemented for `&CycleProbe`.
+    pub struct CycleProbe<T: ?Sized, P: ?Sized>(PhantomData<(*const T, *const P)>);
+
+    impl<T: ?Sized, P: ?Sized> CycleProbe<T, P> {
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    pub trait StrongCycleProbe {
+        #[deprecated(
+            note = "this field holds a type declared with `rc_strong` for this struct; such a reference cycle is never \
+                    dropped"
+        )]
+        fn __fxplus_cycle_check(&self) {}
+    }
+
+    impl<T: ?Sized + RcStrongTo<P>, P: ?Sized> StrongCycleProbe for CycleProbe<T, P> {}
+
+    pub trait NoCycleProbe {
+        fn __fxplus_cycle_check(&self) {}
+    }
+
+    impl<T: ?Sized, P: ?Sized> NoCycleProbe for &CycleProbe<T, P> {}
+}
@@ -89,47 +89,256 @@
 //!
 //! - `child(unwrap)` results in simple `.unwrap()` applied the upgrade call
 //! - `unwrap(expect("Error message")` commands to use `.expect("Error message")`; with this and the above variant we
-//!   always get just the app/parent object
+//!   always get just the app/parent object. The message can include `{child}`, `{parent}`, and `{role}` placeholders
+//!   which are replaced with the names of the agent/child type, the app/parent type, and the role ("application" or
+//!   "parent") at compile time. With `{self}` placeholder the message is formatted at run time and includes the `Debug`
+//!   representation of the agent/child itself, which then must implement `Debug`.
 //! - `unwrap(or(ErrorType, <expression>))` would produce `app`/`parent` methods that return `ErrorType`; the
 //!   particular value returned depends on the `<expression>`. Say, `ErrorType::ParentIsGone` can be used to return a
 //!   specific error code
 //! - `unwrap(or_else(ErrorType, <expr>))` can be used to invoke a method on `self`. `<expr>` can either be just method name
 //!   or something like `map_to_err("argument", 42)` in which case the `map_to_err` method will get the arguments
 //!   specified.
+//! - `unwrap(error)` is a shorthand for the above when there is no need in a custom error type: the methods return
+//!   `Result<Rc<P>, fieldx_plus::ParentGone>`. [`ParentGone`] carries the type names of the agent/child and its
+//!   app/parent, and the role of the latter.
+//! - `unwrap(into(ErrorType))` is similar to `unwrap(error)` but converts `ParentGone` into `ErrorType` with its
+//!   `From<ParentGone>` implementation. This way the project's error type decides how to wrap the failure.
+//! - `unwrap(anyhow)` and `unwrap(eyre)`, available with the `anyhow` and `eyre` features respectively, make the
+//!   methods return `anyhow::Result<Rc<P>>` or `eyre::Result<Rc<P>>` so that `?` works without a custom error type.
+//!   The error wraps `ParentGone` and can be downcast to it.
+//! - `unwrap(default(<expr>))` falls back to the app/parent returned by `<expr>` when the original one is gone; the
+//!   methods keep returning plain `Rc<P>`. Similarly to `or_else`, `<expr>` can be a method name, a path to a function,
+//!   a closure, or an expression.
+//! - `unwrap(or_else_async(ErrorType, <expr>))` is the async counterpart of `or_else` for structs in `async` mode. The
+//!   handler is awaited, so it can do things like flushing state or logging to a remote sink. It is invoked by the
+//!   additional `app_async()`/`parent_async()` method which returns `Result<Rc<P>, ErrorType>`, and by
+//!   `with_app_async()`/`with_parent_async()`. Trait methods cannot be async, so `app()`/`parent()` of the
+//!   `Agent`/`Child` trait and the sync `with_app()`/`with_parent()` return `Result<Rc<P>, ParentGone>` without
+//!   invoking the handler.
+//!
+//! Any of the above can be combined with `also_try`, as in `unwrap(expect("..."), also_try)`, to get an extra
+//! `try_app()`/`try_parent()` method that returns `Option` and never panics. This comes handy in shutdown paths where
+//! the app/parent may be legitimately gone. The `Agent` and `Child` traits provide `try_app`/`try_parent` too.
+//!
+//! Failed upgrades are silent unless the `unwrap` policy panics. With the `tracing` feature enabled, add
+//! `trace(<level>)` to `unwrap`, e.g. `unwrap(or(...), trace(warn))`, and the `app()`/`parent()` methods will emit an
+//! event with the agent/child and app/parent types, the relationship role, and the location of the `fx_plus` attribute
+//! whenever the app/parent is gone. See [`trace`](crate::trace) for details.
+//!
+//! Sometimes an agent or a child has to be created before its app/parent is ready. Declare it with `late_bind`, e.g.
+//! `agent(App, late_bind)`, and it will be built without the app/parent reference which is set later with
+//! `bind_app(&app)`/`bind_parent(&parent)`. Until then `app()`/`parent()` behave as if the app/parent is gone. For
+//! `async` structs there is also `app_ready().await`/`parent_ready().await` which waits until the binding takes place and
+//! returns `None` if the app/parent is gone by then. It never resolves if the binding doesn't happen, so wrap it into a
+//! timeout when that is possible. The builder macros cannot set a late-bound app/parent and refuse to build such
+//! structs. See [`late`](crate::late) for details.
+//!
+//! Every `app()`/`parent()` call returns a new strong reference which can be stored somewhere and extend the life of
+//! the app/parent object. When this is undesirable, declare the agent/child with `scoped`, as in `fx_plus(agent(App),
+//! scoped)`, and use its `with_app(|app| ...)`/`with_parent(|parent| ...)` methods: they upgrade the reference, call
+//! the closure with the object, and drop the strong reference immediately. The return value of these follows the
+//! `unwrap` policy: it is either closure's `R`, or `Option<R>`, or `Result<R, ErrorType>`. For `async` structs there
+//! are also `with_app_async`/`with_parent_async` variants. They take a closure that borrows the object and returns a
+//! boxed future, like `|app| Box::pin(async move { app.foo().await })`; the strong reference is kept by the method
+//! itself and dropped as soon as the future completes. These methods are opt-in because they are inherent and would
+//! otherwise conflict with the struct's own methods of the same names.
+//!
+//! The app/parent type can also be a trait object: `agent(dyn AppApi)` or `child(dyn ParentApi + Send + Sync)`. In this
+//! case the agent/child holds a `Weak<dyn AppApi>` and the builder macros coerce it from whatever concrete app type is
+//! passed in. This allows to keep agent types non-generic and to swap real and mock applications at run time.
+//!
+//! Be careful with `rc_strong`: if the app/parent also holds the agent/child then neither of them is ever dropped. `fx_plus`
+//! emits a compile-time warning when a field of an app/parent struct holds a type declared as `rc_strong` agent/child of
+//! the same struct. Cycles that cannot be seen at compile time can be detected in debug builds: declare the
+//! application with `track_leaks`, as in `fx_plus(app, track_leaks)`, and `fieldx_plus::leaks::LeakGuard` reports its
+//! objects that are still alive when the guard is dropped.
+//!
+//! Since apps and parents are always reference counted, it is often more useful to compare them by identity rather than
+//! by value. `fx_plus(app, identity)` or `fx_plus(parent, identity)` implements `PartialEq`, `Eq`, `Hash`,
+//! `PartialOrd`, and `Ord` based on the object address. The `Agent` and `Child` traits also provide
+//! `same_app(&other)`/`same_parent(&other)` and `is_agent_of(&app)`/`is_child_of(&parent)` methods to find out if
+//! agents/children belong to the same app/parent or to a specific one.
+//!
+//! `app_downgrade()`/`parent_downgrade()` methods return [`AppRef`]/[`ParentRef`] handles. They wrap either
+//! `std::rc::Weak` or `std::sync::Weak`, depending on the struct mode, and provide the same `upgrade()`, `ptr_eq()`,
+//! and `is_alive()` methods for both. A handle can be used as the first argument of the builder macros.
 //!
 //! Helper macros `agent_build`, `agent_builder`, `child_build`, and `child_builder` are wrappers around builder
 //! pattern. I.e.  `agent_build!(self.app(), Agent { foo: 42, bar: "baz" })` is actually a shortcut for
 //! `Agent::builder().app(self.app()).foo(42).bar("baz").build()`. `agent_builder` is the same but without the final
-//! `build()` call.
+//! `build()` call. The macros also work when the relationship is renamed with `as`, e.g. `agent(Server as server)`: the
+//! builder setter is then called `server`, and so are the `server()`/`server_downgrade()` methods which complement the
+//! `Agent` trait's `app()`/`app_downgrade()`.
+//!
+//! The first argument of `agent_build!` and `agent_builder!` is anything implementing [`AppSource`]: the app itself,
+//! `Rc<App>` or `&App`, a `Weak<App>` or an [`AppRef`] handle, or another agent of the same app. For example,
+//! `agent_build!(self, Cache { size: 16 })` called in a method of an agent builds its sibling. A plain parent used as
+//! the app, as in `agent(Registry)`, is an `AppSource` too. The app is picked by the type of the agent being built: in
+//! a method of `Hub`, an agent of `App` which is also a parent, `agent_build!(self, Port)` builds an agent of the hub
+//! if `Port` is declared with `agent(Hub)`, or a sibling of the hub if it is declared with `agent(App)`.
+//!
+//! Similar to the struct update syntax, the macros take `..base` after the fields: `agent_build!(app, Worker { id: 3,
+//! ..worker_defaults() })`. Here `base` is a builder of the same struct, like the one returned by a function that
+//! pre-fills common fields, or a clone of a template builder. The fields listed explicitly and the app/parent override
+//! what is set in the base. Unlike the real struct update syntax, the base must be a builder: a value of the struct,
+//! like `..Worker::default()`, is not accepted.
+//!
+//! `children_build!` and `agents_build!` build a batch of children or agents from an iterator. For example,
+//! `children_build!(self, Shard, specs.iter(), |spec| { id: spec.id, ..shard_defaults() })` builds a `Shard` for every
+//! item of `specs`, with the item bound to `spec` while the fields are evaluated. The result is `Result<Vec<Shard>,
+//! BatchBuildError<E>>`, where [`BatchBuildError`](crate::error::BatchBuildError) carries the index of the first item
+//! that failed to build along with the builder error `E`, available via its `error()` and `into_source()` methods.
+//! The app/parent is downgraded once for the whole batch.
+//!
+//! Without macros, a builder with the app/parent already set is returned by `app.agent_builder::<NetService>()` and
+//! `parent.child_builder::<Child>()` methods of the [`Application`] and [`Parent`] traits, respectively. These are
+//! friendlier to IDEs and can be used in generic code. Each agent and child has its builder type available as `<T as
+//! Agent>::Builder` or `<T as Child>::Builder`. As of now, trait-object apps and parents are only supported by the
+//! macros.
+//!
+//! Deeper hierarchies can be built in one go with the `tree!` macro: `tree!(App::new() => { NetService { port: 4242 }
+//! => { Handler { id: 1 }, Handler { id: 2 } }, Logger })`. Each node is built in order and bound to the enclosing one,
+//! and the result is a [`TreeNode`](crate::tree::TreeNode) of the root with the built nodes in it. Builder errors are
+//! boxed into `Box<dyn std::error::Error>`, while `?` in the field expressions applies to the enclosing function. See
+//! [`tree`](crate::tree) for details.
+//!
+//! Builders of agents and children declared with `build_error`, as in `#[fx_plus(child(Parent), build_error)]`, return
+//! [`BuildError`](crate::error::BuildError) instead of the error type of fieldx. When the app/parent is not set, the
+//! error is `BuildError::ParentNotSet { role, child_type }` rather than the generic "field not set" error; other unset
+//! fields are reported with `BuildError::Field`. Add `check_alive` to the relationship, as in `agent(App,
+//! check_alive)`, and `build()` also fails with `BuildError::ParentGoneAtBuild` if the app/parent is already gone;
+//! `check_alive` implies `build_error`. Reference-counted structs, i.e. apps and parents, cannot use `check_alive`.
+//! Structs with their own builder `error` type or `post_build` method keep the error type of fieldx.
+//!
+//! To catch a missing app/parent at compile time, declare the relationship with `typestate_builder`, e.g.
+//! `child(Parent, typestate_builder)`. The struct then gets a `typestate_builder()` method returning
+//! `<Struct>TypestateBuilder` which only has `build()` once the app/parent setter is called. The builder macros and the
+//! `agent_builder()`/`child_builder()` trait methods return it with the app/parent already set. See
+//! [`typestate`] for details.
+//!
+//! Lazy fields holding agents or children don't need a hand-written builder method. Mark the field with
+//! `#[fx_plus(agent(port = 4242))]` or `#[fx_plus(child(id = 1))]` next to `#[fieldx(lazy)]` and its `build_<field>`
+//! method is generated with `agent_build!`/`child_build!`, passing `field = value` pairs as initializers; values may
+//! refer to `self`. The field type is the agent/child type or an `Rc`/`Arc` of it; the built object is converted into
+//! the field type with `Into`. A field-level `agent` needs an `app` or an `agent` struct, a `child` needs a `parent`.
+//! By default a failed build panics; use `expect("message")` among the arguments to change the panic message. For
+//! `fallible` fields the error is converted into the field's error type with `Into` and returned instead, unless
+//! `expect` is given.
+//!
+//! Fields copying configuration from the app/parent can be declared with `#[fx_plus(inherit = getter)]`, e.g.
+//! `#[fx_plus(inherit = timeout)] timeout: u64` on an agent or a child. When the struct is built, the inherited fields
+//! not given to the builder, like `timeout` in `child_build!(service, Worker { id: 1 })`, are filled with the values of
+//! the named getters of the upgraded app/parent, converted with `Into`. If the app/parent is gone, the field default is
+//! used. The field's `builder` arguments apply to its setter as usual. The values are filled in by the builder's
+//! `post_build`, so `inherit` cannot be used with a custom `post_build`, with `late_bind`, by a struct that is both an
+//! agent and a child, or by a reference-counted struct with a builder error type.
+//!
+//! Arguments repeated on many structs can be declared once per crate with `fieldx_plus::defaults!{...}`. Invoked at the
+//! crate root, e.g. `fieldx_plus::defaults!{ agent(unwrap(or(AppError, AppError::AppIsGone))), sync }`, it generates a
+//! crate-local `fx_plus_defaults!` macro. Items wrapped into it, like `fx_plus_defaults!{ #[fx_plus(agent(App))] struct
+//! Service {} }`, get the defaults merged with the arguments of their `fx_plus` attribute. Explicit arguments win,
+//! including those that conflict with a default: `sync(off)` or `async` discard the default `sync`, and `rc_strong`
+//! discards the default `unwrap`. `agent(...)` and `child(...)` defaults only apply to structs that are agents or
+//! children already. `defaults!` must be invoked at the crate root, elsewhere the generated macro fails to resolve.
+//! `fx_plus_defaults!` only recognizes the attribute by its bare name, i.e. `fx_plus` has to be imported with
+//! `use fieldx_plus::fx_plus;`; a path like `#[fieldx_plus::fx_plus(...)]` is reported as an error.
+//!
+//! Mind that the defaults only reach the items wrapped into `fx_plus_defaults!`: a struct with a bare `#[fx_plus(...)]`
+//! outside of it silently gets none of them, and nothing warns about this. It is a deliberate trade-off: on stable Rust
+//! a declarative macro cannot serve as an attribute, and a procedural macro cannot see the state of the crate it is
+//! used in, so there is no way for `fx_plus` to pick the crate's defaults up on its own. Every struct that is expected
+//! to get the defaults has to be wrapped, one per `fx_plus_defaults!{...}`.
 
+pub mod error;
+pub mod handle;
+pub mod late;
+pub mod leaks;
+pub mod trace;
 pub mod traits;
+pub mod tree;
+pub mod typestate;
 
+#[doc(inline)]
+pub use crate::error::ParentGone;
+#[doc(inline)]
+pub use crate::handle::AppRef;
+#[doc(inline)]
+pub use crate::handle::ParentRef;
+#[doc(inline)]
+pub use crate::handle::WeakPointer;
 #[doc(inline)]
 pub use crate::traits::Agent;
 #[doc(inline)]
+pub use crate::traits::AppSource;
+#[doc(inline)]
 pub use crate::traits::Application;
 #[doc(inline)]
 pub use crate::traits::Child;
 #[doc(inline)]
 pub use crate::traits::Parent;
 #[doc(inline)]
+pub use fieldx_plus_macros::defaults;
+#[doc(inline)]
 pub use fieldx_plus_macros::fx_plus;
 
+// Used by the code generated for `unwrap(anyhow)` and `unwrap(eyre)`.
+#[cfg(feature = "anyhow")]
+#[doc(hidden)]
+pub use anyhow as __anyhow;
+#[cfg(feature = "eyre")]
+#[doc(hidden)]
+pub use eyre as __eyre;
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __fxplus_builder {
-    ($method:ident, $conv_method:ident:
-        $self:expr, $ty:ty $(
+    ($builder_method:ident, $bind_method:ident, $handle:ident:
+        $self:expr, $ty:ty $( { $( $fields:tt )* } )?
+    ) => {
+        $crate::__fxplus_builder!(
+            @bound $builder_method, $bind_method, $handle:
+            $crate::__fxplus_builder!(@weak $handle $self, $ty), $ty $( { $( $fields )* } )?
+        )
+    };
+
+    // The same with the weak reference to the app/parent already at hand.
+    (@bound $builder_method:ident, $bind_method:ident, $handle:ident:
+        $weak:expr, $ty:ty $(
             {
                 $( $field:ident $( : $initializer:expr )? ),* $(,)*
             }
         )?
     ) => {
-        <$ty>::builder()
-            .$method( <$ty>::$conv_method( $self.__fxplus_myself_downgrade() ) )
+        <$ty>::$bind_method(
+            <$ty>::$builder_method(),
+            $crate::$handle::new($weak),
+        )
             $( $( .$field( $crate::__fxplus_builder!(@field_or_expr $field $( : $initializer )? ) ) )* )?
     };
 
+    // Struct update syntax: the fields not listed are taken from the `..base` builder. A struct value is not accepted.
+    (@bound $builder_method:ident, $bind_method:ident, $handle:ident:
+        $weak:expr, $ty:ty {
+            $( $field:ident $( : $initializer:expr )? , )* .. $base:expr $(,)?
+        }
+    ) => {
+        <$ty>::$bind_method(
+            $base,
+            $crate::$handle::new($weak),
+        )
+            $( .$field( $crate::__fxplus_builder!(@field_or_expr $field $( : $initializer )? ) ) )*
+    };
+
+    // An agent's app can be taken from anything implementing `AppSource` for the weak pointer the agent expects.
+    (@weak AppRef $self:expr, $ty:ty) => {{
+        use $crate::traits::AppSource as _;
+        $crate::traits::__private::app_weak_for::<$ty, _>($self.app_weak())
+    }};
+    (@weak ParentRef $self:expr, $ty:ty) => {
+        $self.__fxplus_myself_downgrade()
+    };
+
     (@field_or_expr $field:ident : $initializer:expr) => {
         $initializer
     };
@@ -138,30 +347,117 @@ macro_rules! __fxplus_builder {
     };
 }
 
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __fxplus_batch_build {
+    ($builder_method:ident, $bind_method:ident, $handle:ident:
+        $self:expr, $ty:ty, $iter:expr, | $item:pat_param | { $( $fields:tt )* }
+    ) => {{
+        // The app/parent is downgraded once, the items share clones of the weak reference.
+        let __fxplus_weak = $crate::__fxplus_builder!(@weak $handle $self, $ty);
+        ::std::iter::IntoIterator::into_iter($iter)
+            .enumerate()
+            .map(|(__fxplus_index, $item)| {
+                $crate::__fxplus_builder!(
+                    @bound $builder_method, $bind_method, $handle:
+                    ::std::clone::Clone::clone(&__fxplus_weak), $ty { $( $fields )* }
+                )
+                .build()
+                .map_err(|err| $crate::error::BatchBuildError::new(__fxplus_index, err))
+            })
+            .collect::<::std::result::Result<::std::vec::Vec<_>, _>>()
+    }};
+}
+
 #[macro_export]
 macro_rules! agent_builder {
     ( $( $args:tt )+ ) => {
-        ::fieldx_plus::__fxplus_builder!(app, __fxplus_app: $( $args )+ )
+        ::fieldx_plus::__fxplus_builder!(__fxplus_app_builder, __fxplus_bind_app, AppRef: $( $args )+ )
     };
 }
 
 #[macro_export]
 macro_rules! agent_build {
     ( $( $args:tt )+ ) => {
-        ::fieldx_plus::__fxplus_builder!(app, __fxplus_app: $( $args )+ ).build()
+        ::fieldx_plus::__fxplus_builder!(__fxplus_app_builder, __fxplus_bind_app, AppRef: $( $args )+ ).build()
     };
 }
 
 #[macro_export]
 macro_rules! child_builder {
     ( $( $args:tt )+ ) => {
-        ::fieldx_plus::__fxplus_builder!(parent, __fxplus_parent: $( $args )+ )
+        ::fieldx_plus::__fxplus_builder!(__fxplus_parent_builder, __fxplus_bind_parent, ParentRef: $( $args )+ )
     };
 }
 
 #[macro_export]
 macro_rules! child_build {
     ( $( $args:tt )+ ) => {
-        ::fieldx_plus::__fxplus_builder!(parent, __fxplus_parent: $( $args )+ ).build()
+        ::fieldx_plus::__fxplus_builder!(__fxplus_parent_builder, __fxplus_bind_parent, ParentRef: $( $args )+ ).build()
+    };
+}
+
+#[macro_export]
+macro_rules! agents_build {
+    ( $( $args:tt )+ ) => {
+        ::fieldx_plus::__fxplus_batch_build!(__fxplus_app_builder, __fxplus_bind_app, AppRef: $( $args )+ )
+    };
+}
+
+#[macro_export]
+macro_rules! children_build {
+    ( $( $args:tt )+ ) => {
+        ::fieldx_plus::__fxplus_batch_build!(__fxplus_parent_builder, __fxplus_bind_parent, ParentRef: $( $args )+ )
+    };
+}
+
+#[macro_export]
+macro_rules! tree {
+    ( $root:expr $( => { $( $children:tt )* } )? ) => {
+        // A labeled block rather than a closure, so that `?` and `return` in the user expressions apply to the
+        // enclosing function.
+        'fxplus_tree: {
+            let __fxplus_node = $root;
+            ::std::result::Result::<_, ::std::boxed::Box<dyn ::std::error::Error>>::Ok(
+                $crate::__fxplus_tree!(@node 'fxplus_tree __fxplus_node $( { $( $children )* } )?)
+            )
+        }
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __fxplus_tree {
+    (@node $label:lifetime $node:ident) => {
+        $crate::tree::TreeNode::new($node, ())
+    };
+    (@node $label:lifetime $node:ident { $( $children:tt )* }) => {{
+        let __fxplus_children = $crate::__fxplus_tree!(@children $label $node [] $( $children )*);
+        $crate::tree::TreeNode::new($node, __fxplus_children)
+    }};
+
+    (@children $label:lifetime $parent:ident [ $( $built:expr, )* ] $(,)?) => {
+        ( $( $built, )* )
+    };
+    (@children $label:lifetime $parent:ident [ $( $built:expr, )* ]
+        $ty:path $( { $( $field:ident $( : $initializer:expr )? ),* $(,)? } )? $( => { $( $sub:tt )* } )?
+        $( , $( $rest:tt )* )?
+    ) => {
+        $crate::__fxplus_tree!(@children $label $parent [
+            $( $built, )*
+            {
+                let __fxplus_node =
+                    match <$ty as $crate::tree::__private::BuildUnder<_>>::__fxplus_builder_under(&*$parent)
+                        $( $( .$field( $crate::__fxplus_builder!(@field_or_expr $field $( : $initializer )? ) ) )* )?
+                        .build()
+                    {
+                        ::std::result::Result::Ok(node) => node,
+                        ::std::result::Result::Err(err) => {
+                            break $label ::std::result::Result::Err(::std::convert::From::from(err))
+                        }
+                    };
+                $crate::__fxplus_tree!(@node $label __fxplus_node $( { $( $sub )* } )?)
+            },
+        ] $( $( $rest )* )?)
     };
 }
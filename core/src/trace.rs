@@ -0,0 +1,56 @@
+//! Reporting of failed app/parent upgrades.
+//!
+//! With the `tracing` feature enabled, `app()`/`parent()` methods of agents and children declared with
+//! `unwrap(trace(<level>))` emit an event with `fieldx_plus` target whenever the app/parent object is gone. The event
+//! carries the type names of the agent/child and its app/parent, the role of the latter, and the location of the
+//! `fx_plus` attribute. Without the feature the reporting is compiled out.
+
+use crate::error::Role;
+
+/// Level of the event emitted when an upgrade fails. Mirrors `tracing::Level`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn upgrade_failed<C: ?Sized, P: ?Sized>(level: Level, role: Role, file: &'static str, line: u32, column: u32) {
+    #[cfg(feature = "tracing")]
+    {
+        let child = std::any::type_name::<C>();
+        let parent = std::any::type_name::<P>();
+
+        macro_rules! emit {
+            ($level:expr) => {
+                tracing::event!(
+                    target: "fieldx_plus",
+                    $level,
+                    child,
+                    parent,
+                    %role,
+                    file,
+                    line,
+                    column,
+                    "{role} object `{parent}` of `{child}` is gone"
+                )
+            };
+        }
+
+        match level {
+            Level::Trace => emit!(tracing::Level::TRACE),
+            Level::Debug => emit!(tracing::Level::DEBUG),
+            Level::Info => emit!(tracing::Level::INFO),
+            Level::Warn => emit!(tracing::Level::WARN),
+            Level::Error => emit!(tracing::Level::ERROR),
+        }
+    }
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = (level, role, file, line, column);
+    }
+}
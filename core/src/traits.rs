@@ -1,30 +1,122 @@
+use crate::WeakPointer;
+
 /// This trait is used to declare parent structs in parent-child relationships.
 pub trait Parent {
     /// Weak ref-count type for the parent to hold a reference to itself.
     type WeakSelf;
 
     fn __fxplus_myself_downgrade(&self) -> Self::WeakSelf;
+
+    /// Return a builder of child `C` with the parent already set to `self`. This is the non-macro counterpart of
+    /// [`child_builder!`](crate::child_builder!) which can be used in generic code.
+    fn child_builder<C>(&self) -> C::Builder
+    where
+        C: Child,
+        C::WeakParent: From<Self::WeakSelf>,
+    {
+        C::__fxplus_bind_parent(
+            C::__fxplus_parent_builder(),
+            C::WeakParent::from(self.__fxplus_myself_downgrade()),
+        )
+    }
 }
 
 /// This trait is used to declare child structs in parent-child relationships.
 pub trait Child {
     /// Type of strong reference to the parent.
     type RcParent;
-    /// Type of weak reference to the parent.
+    /// Type of weak reference to the parent, a [`ParentRef`](crate::ParentRef) handle.
     type WeakParent;
     /// For use of the [`child_build!`](crate::child_build!) and [`child_builder!`](crate::child_builder) macros.
     type FXPParent;
+    /// The builder type of the child with the parent already set.
+    type Builder;
+    /// The builder type of the child before the parent is set. It is the same as [`Builder`](Child::Builder) unless
+    /// the child is declared with `typestate_builder`.
+    type UnboundBuilder;
 
     /// Return a strong reference to the parent.
     fn parent(&self) -> Self::RcParent;
     /// Return a weak reference to the parent.
     fn parent_downgrade(&self) -> Self::WeakParent;
+    /// Return a strong reference to the parent, or `None` if it is gone. Unlike [`parent`](Child::parent) it never
+    /// panics, regardless of the `unwrap` policy.
+    fn try_parent(&self) -> Option<<Self::WeakParent as WeakPointer>::Strong>
+    where
+        Self::WeakParent: WeakPointer,
+    {
+        self.parent_downgrade().upgrade()
+    }
+    /// `true` if both children belong to the same parent.
+    fn same_parent<O>(&self, other: &O) -> bool
+    where
+        O: Child<WeakParent = Self::WeakParent> + ?Sized,
+        Self::WeakParent: WeakPointer,
+    {
+        self.parent_downgrade().ptr_eq(&other.parent_downgrade())
+    }
+    /// `true` if `parent` is the parent of this child.
+    fn is_child_of(&self, parent: &<Self::WeakParent as WeakPointer>::Strong) -> bool
+    where
+        Self::WeakParent: WeakPointer,
+    {
+        self.parent_downgrade().ptr_eq(&Self::WeakParent::downgrade(parent))
+    }
     fn __fxplus_parent(parent: Self::WeakParent) -> Self::FXPParent;
+    /// Set the parent on a builder. The setter name depends on the parent base name which can be changed with `as`,
+    /// this method hides the difference from the builder macros.
+    #[doc(hidden)]
+    fn __fxplus_bind_parent(builder: Self::UnboundBuilder, parent: Self::WeakParent) -> Self::Builder;
+    #[doc(hidden)]
+    fn __fxplus_parent_builder() -> Self::UnboundBuilder;
+}
+
+/// This trait is used to declare application structs.
+pub trait Application: Parent {
+    /// Return a builder of agent `A` with the application already set to `self`. This is the non-macro counterpart
+    /// of [`agent_builder!`](crate::agent_builder!).
+    fn agent_builder<A>(&self) -> A::Builder
+    where
+        A: Agent,
+        A::WeakApp: From<Self::WeakSelf>,
+    {
+        A::__fxplus_bind_app(
+            A::__fxplus_app_builder(),
+            A::WeakApp::from(self.__fxplus_myself_downgrade()),
+        )
+    }
+}
+
+/// Anything that can provide a weak reference `W` to an application: the application itself, a reference-counted
+/// pointer to it, a weak pointer or an [`AppRef`](crate::AppRef) handle, or any agent of the application. This lets
+/// [`agent_build!`](crate::agent_build!) and [`agent_builder!`](crate::agent_builder!) take whatever is at hand as
+/// the first argument, e.g. build a sibling agent from within another agent.
+///
+/// The weak pointer type is the one the agent being built expects. A parent that is not an app implements it for a
+/// weak pointer to itself; an agent which is also a parent implements it for both its own app and itself, so it can
+/// build its sibling agents as well as agents of its own.
+pub trait AppSource<W> {
+    /// Return a weak reference to the application.
+    fn app_weak(&self) -> W;
+}
+
+impl<T: Application + ?Sized> AppSource<T::WeakSelf> for T {
+    fn app_weak(&self) -> T::WeakSelf {
+        self.__fxplus_myself_downgrade()
+    }
 }
 
-/// This trait is used to declare application structs. For now it is just a marker trait,
-/// but it may be extended in the future to include application-specific methods or properties.
-pub trait Application: Parent {}
+impl<T: ?Sized> AppSource<std::rc::Weak<T>> for std::rc::Weak<T> {
+    fn app_weak(&self) -> Self {
+        self.clone()
+    }
+}
+
+impl<T: ?Sized> AppSource<std::sync::Weak<T>> for std::sync::Weak<T> {
+    fn app_weak(&self) -> Self {
+        self.clone()
+    }
+}
 
 /// This trait is used to declare agents that can access the application. Technically, it is identical to the
 /// [`Child`](crate::Child) trait, but it is used to distinguish _agents_ from _children_ because an agent
@@ -33,14 +125,80 @@ pub trait Application: Parent {}
 pub trait Agent {
     /// Type of strong reference to the application.
     type RcApp;
-    /// Type of weak reference to the application.
+    /// Type of weak reference to the application, an [`AppRef`](crate::AppRef) handle.
     type WeakApp;
     /// For use of the [`agent_build!`](crate::agent_build!) and [`agent_builder!`](crate::agent_builder) macros.
     type FXPApp;
+    /// The builder type of the agent with the application already set.
+    type Builder;
+    /// The builder type of the agent before the application is set. It is the same as [`Builder`](Agent::Builder)
+    /// unless the agent is declared with `typestate_builder`.
+    type UnboundBuilder;
 
     /// Return a strong reference to the application.
     fn app(&self) -> Self::RcApp;
     /// Return a weak reference to the application.
     fn app_downgrade(&self) -> Self::WeakApp;
+    /// Return a strong reference to the application, or `None` if it is gone. Unlike [`app`](Agent::app) it never
+    /// panics, regardless of the `unwrap` policy.
+    fn try_app(&self) -> Option<<Self::WeakApp as WeakPointer>::Strong>
+    where
+        Self::WeakApp: WeakPointer,
+    {
+        self.app_downgrade().upgrade()
+    }
+    /// `true` if both agents belong to the same application.
+    fn same_app<O>(&self, other: &O) -> bool
+    where
+        O: Agent<WeakApp = Self::WeakApp> + ?Sized,
+        Self::WeakApp: WeakPointer,
+    {
+        self.app_downgrade().ptr_eq(&other.app_downgrade())
+    }
+    /// `true` if `app` is the application of this agent.
+    fn is_agent_of(&self, app: &<Self::WeakApp as WeakPointer>::Strong) -> bool
+    where
+        Self::WeakApp: WeakPointer,
+    {
+        self.app_downgrade().ptr_eq(&Self::WeakApp::downgrade(app))
+    }
     fn __fxplus_app(app: Self::WeakApp) -> Self::FXPApp;
+    /// Set the application on a builder. See [`Child::__fxplus_bind_parent`].
+    #[doc(hidden)]
+    fn __fxplus_bind_app(builder: Self::UnboundBuilder, app: Self::WeakApp) -> Self::Builder;
+    #[doc(hidden)]
+    fn __fxplus_app_builder() -> Self::UnboundBuilder;
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use crate::Agent;
+    use crate::AppRef;
+    use crate::WeakPointer;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    /// Pin the weak pointer type taken from an [`AppSource`](crate::AppSource) to the one agent `A` expects.
+    #[inline(always)]
+    pub fn app_weak_for<A, W>(weak: W) -> W
+    where
+        A: Agent<WeakApp = AppRef<W>> + ?Sized,
+        W: WeakPointer,
+    {
+        weak
+    }
+
+    /// Maps the type of a field declared with field-level `agent(...)`/`child(...)` to the type to build. Agents and
+    /// children implement it for themselves, reference-counted pointers for their pointee.
+    pub trait LazyTarget {
+        type Target;
+    }
+
+    impl<T> LazyTarget for Rc<T> {
+        type Target = T;
+    }
+
+    impl<T> LazyTarget for Arc<T> {
+        type Target = T;
+    }
 }
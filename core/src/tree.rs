@@ -0,0 +1,101 @@
+//! Declarative construction of app/parent hierarchies.
+//!
+//! [`tree!`](crate::tree!) takes the root object followed by a nested description of its agents and children:
+//!
+//! ```
+//! use fieldx_plus::fx_plus;
+//! use fieldx_plus::tree;
+//! use fieldx_plus::tree::TreeNode;
+//!
+//! #[fx_plus(app)]
+//! struct App {}
+//!
+//! #[fx_plus(agent(App, unwrap), parent)]
+//! struct NetService {
+//!     port: u16,
+//! }
+//!
+//! #[fx_plus(child(NetService, unwrap))]
+//! struct Handler {
+//!     #[fieldx(get(copy))]
+//!     id: u32,
+//! }
+//!
+//! #[fx_plus(agent(App, unwrap))]
+//! struct Logger {}
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let tree = tree!(App::new() => {
+//!         NetService { port: 4242 } => {
+//!             Handler { id: 1 },
+//!             Handler { id: 2 },
+//!         },
+//!         Logger,
+//!     })?;
+//!
+//!     // Destructuring names the nodes of interest. `TreeNode` also dereferences to the object of the node.
+//!     let TreeNode { children: (service, logger), .. } = &tree;
+//!     let (_, second_handler) = &service.children;
+//!     assert_eq!(second_handler.id(), 2);
+//!     assert!(std::rc::Rc::ptr_eq(&logger.app(), &tree.node));
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Every node is built in the order of appearance with the builder of its type, and its app/parent is set to the
+//! enclosing node. Whether a node is an agent or a child of its parent is determined by its `fx_plus` declaration.
+//! Nodes that have children of their own must be apps or parents. The fields are set with the same syntax as in
+//! [`agent_build!`](crate::agent_build!), including the shorthand form.
+//!
+//! The result is a [`TreeNode`] of the root, or the first error reported by a builder. Since the [`TreeNode`] holds
+//! strong references to the parent nodes, they stay alive as long as the tree itself.
+//!
+//! **Note** that the builders of different nodes may report different error types, therefore the error is boxed into
+//! `Box<dyn std::error::Error>`; use `downcast_ref` to get the original one. This only concerns the builder errors:
+//! the field expressions are evaluated in the enclosing function, so `?` and `return` in them work as usual.
+//!
+//! Agents and children with a trait object app/parent type, or declared with `late_bind`, cannot be tree nodes. A node
+//! which is both an agent and a child only gets the app and the parent bound if they are of the same type; otherwise
+//! only its relationship with the enclosing node is set.
+
+use std::ops::Deref;
+
+/// A built node of a tree along with its subtrees. Dereferences to the object of the node.
+#[derive(Debug, Clone)]
+pub struct TreeNode<T, C = ()> {
+    /// The object built for the node. For apps and parents this is a reference-counted pointer.
+    pub node:     T,
+    /// Tuple of the subtrees of the node, in the order of declaration.
+    pub children: C,
+}
+
+impl<T, C> Deref for TreeNode<T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.node
+    }
+}
+
+impl<T, C> TreeNode<T, C> {
+    /// Create a node with the subtrees.
+    pub fn new(node: T, children: C) -> Self {
+        Self { node, children }
+    }
+
+    /// Consume the node and return the object built for it.
+    pub fn into_node(self) -> T {
+        self.node
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    /// Implemented by agents and children for their app/parent type `P`. Returns the builder with `parent` already
+    /// set as the app/parent.
+    pub trait BuildUnder<P: ?Sized> {
+        type Builder;
+
+        fn __fxplus_builder_under(parent: &P) -> Self::Builder;
+    }
+}
@@ -0,0 +1,58 @@
+//! Marker types of typestate builders.
+//!
+//! An agent or a child declared with `typestate_builder`, e.g. `child(Parent, typestate_builder)`, gets an additional
+//! builder type named after the struct with `TypestateBuilder` suffix. It is returned by the `typestate_builder()`
+//! method and by the builder macros, and it only has the `build()` method once the app/parent is set:
+//!
+//! ```
+//! use fieldx_plus::child_builder;
+//! use fieldx_plus::fx_plus;
+//!
+//! #[fx_plus(app)]
+//! struct Pool {}
+//!
+//! #[fx_plus(child(Pool, unwrap, typestate_builder))]
+//! struct Worker {
+//!     #[fieldx(get(copy))]
+//!     id: u32,
+//! }
+//!
+//! let pool = Pool::new();
+//! let worker = Worker::typestate_builder()
+//!     .id(1)
+//!     .parent(std::rc::Rc::downgrade(&pool))
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(worker.id(), 1);
+//!
+//! // The macros produce a builder with the parent already set.
+//! let worker = child_builder!(pool, Worker { id: 2 }).build().unwrap();
+//! assert_eq!(worker.id(), 2);
+//! ```
+//!
+//! Without the `.parent(...)` call the chain above would not compile:
+//!
+//! ```compile_fail
+//! # use fieldx_plus::fx_plus;
+//! # #[fx_plus(app)]
+//! # struct Pool {}
+//! # #[fx_plus(child(Pool, unwrap, typestate_builder))]
+//! # struct Worker {
+//! #     #[fieldx(get(copy))]
+//! #     id: u32,
+//! # }
+//! // error[E0599]: no method named `build` found for struct `WorkerTypestateBuilder<Unbound>`
+//! let worker = Worker::typestate_builder().id(1).build();
+//! ```
+//!
+//! The field setters can be called in any state. Their names, as well as the name of the app/parent setter, follow the
+//! builder `prefix` of the struct. The regular `builder()` method is still available and checks for the parent at run
+//! time.
+
+/// The state of a typestate builder before the app/parent is set.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Unbound;
+
+/// The state of a typestate builder once the app/parent is set.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bound;
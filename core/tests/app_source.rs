@@ -0,0 +1,103 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::agent_builder;
+use fieldx_plus::fx_plus;
+use fieldx_plus::Agent;
+use std::rc::Rc;
+
+#[fx_plus(app)]
+struct App {
+    #[fieldx(get(copy), default(7))]
+    id: u32,
+}
+
+#[fx_plus(agent(App, unwrap))]
+struct Storage {}
+
+impl Storage {
+    fn cache(&self) -> Cache {
+        // Build a sibling agent of the same app.
+        agent_build!(self, Cache { size: 16 }).unwrap()
+    }
+}
+
+#[fx_plus(agent(App, unwrap))]
+struct Cache {
+    #[fieldx(get(copy))]
+    size: usize,
+}
+
+// A plain parent serving as the app of an agent.
+#[fx_plus(parent)]
+struct Registry {}
+
+#[fx_plus(agent(Registry, unwrap))]
+struct Entry {
+    #[fieldx(get(copy))]
+    key: u32,
+}
+
+// An agent which is also a parent provides both its own app and itself, depending on the agent being built.
+#[fx_plus(agent(App, unwrap), parent)]
+struct Hub {}
+
+impl Hub {
+    fn port(&self) -> Port {
+        agent_build!(self, Port).unwrap()
+    }
+
+    fn cache(&self) -> Cache {
+        agent_build!(self, Cache { size: 8 }).unwrap()
+    }
+}
+
+#[fx_plus(agent(Hub, unwrap))]
+struct Port {}
+
+#[test]
+fn build_from_any_app_source() {
+    let app: Rc<App> = App::new();
+
+    let from_rc = agent_build!(app, Storage).unwrap();
+    let from_ref = agent_build!(&*app, Storage).unwrap();
+    let from_weak = agent_build!(Rc::downgrade(&app), Storage).unwrap();
+    let from_handle = agent_build!(from_rc.app_downgrade(), Storage).unwrap();
+    let from_agent = agent_builder!(from_ref, Cache { size: 32 }).build().unwrap();
+    let sibling = from_weak.cache();
+
+    for storage in [&from_rc, &from_ref, &from_weak, &from_handle] {
+        assert!(Rc::ptr_eq(&storage.app(), &app));
+    }
+    assert_eq!(from_agent.app().id(), 7);
+    assert_eq!(from_agent.size(), 32);
+    assert_eq!(sibling.app().id(), 7);
+    assert_eq!(sibling.size(), 16);
+}
+
+#[test]
+fn parent_as_app() {
+    let registry: Rc<Registry> = Registry::new();
+    let entry = agent_build!(registry, Entry { key: 1 }).unwrap();
+    assert_eq!(entry.key(), 1);
+    assert!(Rc::ptr_eq(&entry.app(), &registry));
+
+    let app: Rc<App> = App::new();
+    let hub: Rc<Hub> = agent_build!(app, Hub).unwrap();
+    let port = agent_build!(hub, Port).unwrap();
+    assert!(Rc::ptr_eq(&port.app(), &hub));
+}
+
+#[test]
+fn agent_parent_builds_its_agents() {
+    let app: Rc<App> = App::new();
+    let hub: Rc<Hub> = agent_build!(app, Hub).unwrap();
+
+    let port = hub.port();
+    assert!(Rc::ptr_eq(&port.app(), &hub));
+
+    let cache = hub.cache();
+    assert!(Rc::ptr_eq(&cache.app(), &app));
+    assert_eq!(cache.size(), 8);
+
+    let from_weak = agent_build!(Rc::downgrade(&hub), Port).unwrap();
+    assert!(Rc::ptr_eq(&from_weak.app(), &hub));
+}
@@ -0,0 +1,46 @@
+#![cfg(any(feature = "async-tokio", feature = "async-lock"))]
+use fieldx_plus::fx_plus;
+use std::sync::Arc;
+
+#[fx_plus(app, r#async)]
+struct MyApp {
+    #[fieldx(get(copy), default(42))]
+    answer: u32,
+}
+
+#[fx_plus(agent(MyApp, late_bind), r#async)]
+struct EarlyAgent {}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn app_ready() {
+    let agent = Arc::new(EarlyAgent::builder().build().unwrap());
+
+    let waiter = tokio::spawn({
+        let agent = Arc::clone(&agent);
+        async move { agent.app_ready().await.map(|app| app.answer()) }
+    });
+
+    tokio::task::yield_now().await;
+    assert!(!waiter.is_finished(), "the app is not bound yet");
+
+    let app = MyApp::new();
+    agent.bind_app(&app);
+
+    assert_eq!(waiter.await.unwrap(), Some(42));
+    // Already bound agent doesn't wait.
+    assert!(agent.app_ready().await.is_some());
+
+    drop(app);
+
+    assert!(agent.app_ready().await.is_none());
+}
+
+#[cfg(feature = "async-tokio")]
+#[tokio::test(start_paused = true)]
+async fn never_bound() {
+    let agent = EarlyAgent::builder().build().unwrap();
+
+    // Without binding the wait can only be limited from the outside.
+    let ready = tokio::time::timeout(std::time::Duration::from_secs(1), agent.app_ready()).await;
+    assert!(ready.is_err(), "an unbound agent must not resolve app_ready()");
+}
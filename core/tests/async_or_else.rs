@@ -0,0 +1,65 @@
+#![cfg(feature = "async")]
+use fieldx_plus::agent_build;
+use fieldx_plus::error::Role;
+use fieldx_plus::fx_plus;
+use fieldx_plus::ParentGone;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq)]
+enum AppError {
+    AppGone(usize),
+}
+
+#[fx_plus(app, r#async)]
+struct MyApp {
+    #[fieldx(get(copy), default(42))]
+    answer: u32,
+}
+
+#[fx_plus(agent(MyApp, unwrap(or_else_async(AppError, app_gone))), r#async, scoped)]
+struct MyAgent {
+    #[fieldx(get(off), default(AtomicUsize::new(0)))]
+    flushed: AtomicUsize,
+}
+
+impl MyAgent {
+    async fn app_gone(&self) -> AppError {
+        tokio::task::yield_now().await;
+        AppError::AppGone(self.flushed.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+}
+
+#[tokio::test]
+async fn async_handler() {
+    let app: Arc<MyApp> = MyApp::new();
+    let agent = agent_build!(app, MyAgent).unwrap();
+
+    assert_eq!(agent.app_async().await.map(|app| app.answer()), Ok(42));
+    assert_eq!(
+        agent.with_app_async(|app| Box::pin(async move { app.answer() })).await,
+        Ok(42)
+    );
+    // The trait method cannot run the async handler.
+    assert!(agent.app().is_ok());
+    assert_eq!(agent.flushed.load(Ordering::SeqCst), 0);
+
+    drop(app);
+
+    assert_eq!(agent.app_async().await.err(), Some(AppError::AppGone(1)));
+    assert_eq!(
+        agent.with_app_async(|app| Box::pin(async move { app.answer() })).await,
+        Err(AppError::AppGone(2))
+    );
+    assert_eq!(
+        agent.app().err(),
+        Some(ParentGone::for_role::<MyAgent, MyApp>(Role::App))
+    );
+    assert_eq!(
+        agent.with_app(|app| app.answer()),
+        Err(ParentGone::for_role::<MyAgent, MyApp>(Role::App))
+    );
+    // Only the async paths invoke the handler.
+    assert_eq!(agent.flushed.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,56 @@
+use fieldx_plus::agents_build;
+use fieldx_plus::children_build;
+use fieldx_plus::fx_plus;
+use std::rc::Rc;
+
+#[fx_plus(app)]
+struct Cluster {}
+
+#[fx_plus(agent(Cluster, unwrap), parent)]
+struct Pool {}
+
+#[fx_plus(child(Pool, unwrap))]
+struct Shard {
+    #[fieldx(get(copy))]
+    id:   u32,
+    #[fieldx(get(clone), into)]
+    name: String,
+}
+
+#[test]
+fn batch_build() {
+    let cluster: Rc<Cluster> = Cluster::new();
+    let pools: Vec<Rc<Pool>> = agents_build!(cluster, Pool, 0..2, |_| {}).unwrap();
+    assert_eq!(pools.len(), 2);
+    assert!(pools.iter().all(|pool| Rc::ptr_eq(&pool.app(), &cluster)));
+
+    let pool = &pools[0];
+    let specs = [(1, "one"), (2, "two"), (3, "three")];
+    let shards = children_build!(pool, Shard, specs.iter(), |(id, name)| { id: *id, name: *name }).unwrap();
+
+    assert_eq!(shards.iter().map(|s| s.id()).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(shards[2].name(), "three");
+    assert!(shards.iter().all(|s| Rc::ptr_eq(&s.parent(), pool)));
+}
+
+#[test]
+fn batch_build_failure() {
+    let cluster: Rc<Cluster> = Cluster::new();
+    let pool = fieldx_plus::agent_build!(cluster, Pool).unwrap();
+
+    // The third item lacks the name.
+    let names = [Some("one"), Some("two"), None, Some("four")];
+    let err = children_build!(pool, Shard, names, |name| {
+        id: 0,
+        ..match name {
+            Some(name) => Shard::builder().name(name),
+            None => Shard::builder(),
+        }
+    })
+    .err()
+    .expect("a shard without name must fail");
+
+    assert_eq!(err.index(), 2);
+    assert_eq!(err.to_string(), format!("failed to build item #2: {}", err.error()));
+    assert!(std::error::Error::source(&err).is_some());
+}
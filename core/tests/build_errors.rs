@@ -0,0 +1,72 @@
+use fieldx_plus::agent_builder;
+use fieldx_plus::child_build;
+use fieldx_plus::error::BuildError;
+use fieldx_plus::error::Role;
+use fieldx_plus::fx_plus;
+use std::rc::Rc;
+
+#[fx_plus(app)]
+struct App {}
+
+#[fx_plus(agent(App, unwrap), parent, build_error)]
+struct Service {
+    #[fieldx(default(80))]
+    port: u16,
+}
+
+#[fx_plus(child(Service, unwrap), build_error)]
+struct Worker {
+    #[fieldx(get(copy))]
+    id: u32,
+}
+
+#[fx_plus(agent(App, unwrap, check_alive))]
+struct Monitor {}
+
+// Without `build_error` the builder error of fieldx is kept.
+#[fx_plus(child(Service, unwrap))]
+struct PlainWorker {}
+
+#[test]
+fn parent_not_set() {
+    let err = Worker::builder().id(1).build().err().expect("the parent is not set");
+    assert!(
+        matches!(err, BuildError::ParentNotSet { role: Role::Parent, child_type } if child_type.ends_with("Worker")),
+        "unexpected error: {err:?}"
+    );
+
+    let err = Service::builder().build().err().expect("the app is not set");
+    assert!(matches!(err, BuildError::ParentNotSet { role: Role::App, .. }));
+
+    let err: fieldx::error::FieldXError = PlainWorker::builder().build().err().expect("the parent is not set");
+    assert!(err.to_string().contains("parent"), "unexpected error: {err}");
+}
+
+#[test]
+fn field_not_set() {
+    let app: Rc<App> = App::new();
+    let service: Rc<Service> = agent_builder!(app, Service).build().unwrap();
+
+    let err = child_build!(service, Worker).err().expect("`id` is not set");
+    assert!(matches!(err, BuildError::Field(_)), "unexpected error: {err:?}");
+
+    let worker = child_build!(service, Worker { id: 1 }).unwrap();
+    assert_eq!(worker.id(), 1);
+    assert_eq!(worker.parent().port, 80);
+}
+
+#[test]
+fn parent_gone_at_build() {
+    let app: Rc<App> = App::new();
+    let mut builder = agent_builder!(app, Monitor);
+    drop(app);
+
+    let err = builder.build().err().expect("the app is gone");
+    match err {
+        BuildError::ParentGoneAtBuild(gone) => {
+            assert_eq!(gone.role(), Role::App);
+            assert!(gone.parent_type().ends_with("App"));
+        }
+        _ => panic!("unexpected error: {err:?}"),
+    }
+}
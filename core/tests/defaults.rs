@@ -0,0 +1,75 @@
+#![cfg(feature = "sync")]
+use fieldx_plus::agent_build;
+use fieldx_plus::child_build;
+use fieldx_plus::fx_plus;
+use std::rc::Rc;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+enum AppError {
+    #[error("The app is gone")]
+    AppIsGone,
+}
+
+fieldx_plus::defaults! { agent(unwrap(or(AppError, AppError::AppIsGone))), sync }
+
+fx_plus_defaults! {
+    #[fx_plus(app)]
+    struct MyApp {
+        #[fieldx(get(copy), default(42))]
+        answer: u32,
+    }
+}
+
+fx_plus_defaults! {
+    #[derive(Debug)]
+    #[fx_plus(agent(MyApp), parent)]
+    struct DefaultAgent {}
+}
+
+fx_plus_defaults! {
+    // The explicit arguments win.
+    #[fx_plus(agent(MyApp, unwrap(expect("The app is gone"))), parent)]
+    struct ExplicitAgent {}
+}
+
+fx_plus_defaults! {
+    // `unwrap` is not compatible with `rc_strong`, the default is not used.
+    #[fx_plus(agent(MyApp, rc_strong))]
+    struct StrongAgent {}
+}
+
+fx_plus_defaults! {
+    // Agent defaults do not apply to children.
+    #[fx_plus(child(DefaultAgent))]
+    struct PlainChild {}
+}
+
+fx_plus_defaults! {
+    // Explicit `sync(off)` overrides the default `sync`.
+    #[fx_plus(app, sync(off))]
+    struct LocalApp {}
+}
+
+#[test]
+fn defaults_apply() {
+    let app: Arc<MyApp> = MyApp::new();
+    let agent: Arc<DefaultAgent> = agent_build!(app, DefaultAgent).unwrap();
+    let explicit = agent_build!(app, ExplicitAgent).unwrap();
+    let strong = agent_build!(app, StrongAgent).unwrap();
+    let child = child_build!(agent, PlainChild).unwrap();
+
+    assert_eq!(agent.app().map(|app| app.answer()), Ok(42));
+    assert_eq!(explicit.app().answer(), 42);
+    assert!(child.parent().is_some());
+
+    let _local: Rc<LocalApp> = LocalApp::new();
+
+    drop(app);
+
+    assert_eq!(strong.app().answer(), 42, "rc_strong agent keeps the app alive");
+    drop(strong);
+
+    assert_eq!(agent.app().err(), Some(AppError::AppIsGone));
+}
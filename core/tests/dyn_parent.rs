@@ -0,0 +1,73 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::child_build;
+use fieldx_plus::fx_plus;
+use std::fmt::Debug;
+
+trait AppCfg {
+    fn cfg(&self) -> String;
+}
+
+trait ParentApi: Debug {
+    fn name(&self) -> String;
+}
+
+#[fx_plus(app)]
+struct MyApp {}
+
+impl AppCfg for MyApp {
+    fn cfg(&self) -> String {
+        "app cfg".to_string()
+    }
+}
+
+#[fx_plus(app)]
+struct MockApp {}
+
+impl AppCfg for MockApp {
+    fn cfg(&self) -> String {
+        "mock cfg".to_string()
+    }
+}
+
+#[fx_plus(agent(dyn AppCfg, unwrap), parent, scoped)]
+#[derive(Debug)]
+struct AnAgent {}
+
+impl AnAgent {
+    pub fn check_cfg(&self) -> String {
+        self.app().cfg()
+    }
+}
+
+impl ParentApi for AnAgent {
+    fn name(&self) -> String {
+        "agent".to_string()
+    }
+}
+
+#[fx_plus(child(dyn ParentApi + 'static), scoped)]
+struct AChild {}
+
+#[test]
+fn dyn_app() {
+    let app = MyApp::new();
+    let mock = MockApp::new();
+    let agents = [
+        agent_build!(app, AnAgent).unwrap(),
+        agent_build!(mock, AnAgent).unwrap(),
+    ];
+
+    assert_eq!(agents[0].check_cfg(), "app cfg");
+    assert_eq!(agents[1].check_cfg(), "mock cfg");
+    assert_eq!(agents[1].with_app(|app| app.cfg()), "mock cfg");
+}
+
+#[test]
+fn dyn_parent() {
+    let app = MyApp::new();
+    let agent = agent_build!(app, AnAgent).unwrap();
+    let child = child_build!(agent, AChild).unwrap();
+
+    assert_eq!(child.parent().unwrap().name(), "agent");
+    assert_eq!(child.with_parent(|parent| parent.name()), Some("agent".to_string()));
+}
@@ -0,0 +1,59 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::child_build;
+use fieldx_plus::fx_plus;
+use std::panic::catch_unwind;
+use std::panic::AssertUnwindSafe;
+
+#[fx_plus(app, sync(off))]
+struct MyApp {}
+
+#[fx_plus(
+    agent(MyApp, unwrap(expect("{role} `{parent}` of {child} is gone"))),
+    parent,
+    sync(off)
+)]
+struct MyAgent {}
+
+#[fx_plus(child(MyAgent, unwrap(expect("{parent} of {child} {self} is gone"))), sync(off))]
+#[derive(Debug)]
+struct MyChild {
+    #[fieldx(get(copy), builder(required))]
+    id: u32,
+}
+
+fn panic_message<R, F: FnOnce() -> R>(f: F) -> String {
+    let payload = catch_unwind(AssertUnwindSafe(f)).err().expect("the closure must panic");
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+        .unwrap()
+}
+
+#[test]
+fn static_template() {
+    let app = MyApp::new();
+    let agent = agent_build!(app, MyAgent).unwrap();
+
+    drop(app);
+
+    assert_eq!(panic_message(|| agent.app()), "application `MyApp` of MyAgent is gone");
+}
+
+#[test]
+fn runtime_template() {
+    let app = MyApp::new();
+    let agent = agent_build!(app, MyAgent).unwrap();
+    let child = child_build!(agent, MyChild { id: 13 }).unwrap();
+
+    assert_eq!(child.id(), 13);
+
+    drop(agent);
+
+    let message = panic_message(|| child.parent());
+    assert!(
+        message.starts_with("MyAgent of MyChild MyChild {") && message.ends_with("} is gone"),
+        "unexpected message: {message}"
+    );
+    assert!(message.contains("id: 13"));
+}
@@ -0,0 +1,63 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::fx_plus;
+use std::rc::Rc;
+
+#[fx_plus(app, sync(off), builder)]
+struct MyApp {
+    #[fieldx(get(copy), default(42))]
+    answer: u32,
+}
+
+impl MyApp {
+    fn stub() -> Rc<Self> {
+        Self::builder().answer(0).build().unwrap()
+    }
+}
+
+#[fx_plus(agent(MyApp, unwrap(default(MyApp::stub))), sync(off), scoped)]
+struct StaticFallback {}
+
+#[fx_plus(agent(MyApp, unwrap(default(fallback))), sync(off), scoped)]
+struct MethodFallback {
+    #[fieldx(get(clone), builder(required))]
+    fallback_app: Rc<MyApp>,
+}
+
+impl MethodFallback {
+    fn fallback(&self) -> Rc<MyApp> {
+        self.fallback_app()
+    }
+}
+
+#[test]
+fn static_fallback() {
+    let app = MyApp::new();
+    let agent = agent_build!(app, StaticFallback).unwrap();
+
+    assert_eq!(agent.app().answer(), 42);
+    assert_eq!(agent.with_app(|app| app.answer()), 42);
+
+    drop(app);
+
+    assert_eq!(agent.app().answer(), 0);
+    assert_eq!(agent.with_app(|app| app.answer()), 0);
+}
+
+#[test]
+fn method_fallback() {
+    let stub = MyApp::stub();
+    let app = MyApp::new();
+    let agent = agent_build!(
+        app,
+        MethodFallback {
+            fallback_app: stub.clone(),
+        }
+    )
+    .unwrap();
+
+    assert!(Rc::ptr_eq(&agent.app(), &app));
+
+    drop(app);
+
+    assert!(Rc::ptr_eq(&agent.app(), &stub));
+}
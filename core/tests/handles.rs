@@ -0,0 +1,38 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::fx_plus;
+use fieldx_plus::AppRef;
+use fieldx_plus::WeakPointer;
+
+#[fx_plus(app, sync(off))]
+struct MyApp {}
+
+#[fx_plus(agent(MyApp, unwrap), sync(off))]
+struct AnAgent {}
+
+#[fx_plus(agent(MyApp, rc_strong), sync(off))]
+struct StrongAgent {}
+
+// Doesn't care about the mode of the app.
+fn app_alive<W: WeakPointer>(app_ref: &AppRef<W>) -> bool {
+    app_ref.is_alive() && app_ref.upgrade().is_some()
+}
+
+#[test]
+fn handles() {
+    let app = MyApp::new();
+    let agent = agent_build!(app, AnAgent).unwrap();
+    let app_ref = agent.app_downgrade();
+
+    assert!(app_alive(&app_ref));
+    assert!(app_ref.ptr_eq(&agent_build!(app, StrongAgent).unwrap().app_downgrade()));
+    assert_eq!(format!("{app_ref:?}"), "AppRef<handles::MyApp> { alive: true }");
+
+    // A handle can be used to build another agent.
+    let sibling = agent_build!(app_ref, AnAgent).unwrap();
+    assert!(sibling.same_app(&agent));
+
+    drop(app);
+
+    assert!(!app_alive(&app_ref));
+    assert_eq!(format!("{app_ref:?}"), "AppRef<handles::MyApp> { alive: false }");
+}
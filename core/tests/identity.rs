@@ -0,0 +1,68 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::child_build;
+use fieldx_plus::fx_plus;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+#[fx_plus(app, identity, sync(off))]
+struct MyApp {
+    #[fieldx(get(copy), default(1))]
+    value: u32,
+}
+
+#[fx_plus(agent(MyApp, unwrap), parent, identity, sync(off))]
+struct AnAgent {}
+
+#[fx_plus(agent(MyApp, unwrap), sync(off))]
+struct OtherAgent {}
+
+// Kinship methods are provided by the traits and don't conflict with the user's own.
+impl OtherAgent {
+    fn same_app(&self) -> bool {
+        false
+    }
+}
+
+#[fx_plus(child(AnAgent, unwrap), sync(off))]
+struct AChild {}
+
+#[test]
+fn app_identity() {
+    let app1 = MyApp::new();
+    let app2 = MyApp::new();
+
+    assert!(*app1 == *Rc::clone(&app1));
+    assert!(*app1 != *app2, "equal values but different objects");
+    assert_eq!(app1.value(), app2.value());
+
+    let set: HashSet<Rc<MyApp>> = [app1.clone(), app2.clone(), app1.clone()].into_iter().collect();
+    assert_eq!(set.len(), 2);
+
+    let ordered: BTreeSet<Rc<MyApp>> = [app2.clone(), app1.clone(), app2.clone()].into_iter().collect();
+    assert_eq!(ordered.len(), 2);
+}
+
+#[test]
+fn kinship() {
+    let app1 = MyApp::new();
+    let app2 = MyApp::new();
+
+    let agent1 = agent_build!(app1, AnAgent).unwrap();
+    let agent2 = agent_build!(app2, AnAgent).unwrap();
+    let other1 = agent_build!(app1, OtherAgent).unwrap();
+
+    assert!(agent1.same_app(&other1));
+    assert!(fieldx_plus::Agent::same_app(&other1, &*agent1));
+    assert!(!other1.same_app());
+    assert!(!agent2.same_app(&other1));
+    assert!(agent1.is_agent_of(&app1));
+    assert!(!agent1.is_agent_of(&app2));
+
+    let child1 = child_build!(agent1, AChild).unwrap();
+    let child2 = child_build!(agent1, AChild).unwrap();
+    assert!(child1.same_parent(&child2));
+    assert!(child1.is_child_of(&agent1));
+    assert!(!child1.is_child_of(&agent2));
+    assert!(agent1 != agent2);
+}
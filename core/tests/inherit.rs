@@ -0,0 +1,177 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::child_build;
+use fieldx_plus::child_builder;
+use fieldx_plus::error::BuildError;
+use fieldx_plus::fx_plus;
+use std::rc::Rc;
+
+#[fx_plus(app)]
+struct App {
+    #[fieldx(get(copy), default(3))]
+    log_level: u8,
+}
+
+#[fx_plus(agent(App, unwrap), parent)]
+struct Service {
+    #[fx_plus(inherit = log_level)]
+    #[fieldx(get(copy))]
+    log_level: u8,
+    #[fieldx(get(copy), default(30))]
+    timeout:   u32,
+    #[fieldx(get(clone), default("svc".to_string()))]
+    prefix:    String,
+}
+
+impl Service {
+    fn name_prefix(&self) -> &str {
+        &self.prefix
+    }
+}
+
+#[fx_plus(child(Service, unwrap))]
+struct Worker {
+    #[fieldx(get(copy))]
+    id:      u32,
+    #[fx_plus(inherit = timeout)]
+    #[fieldx(get(copy))]
+    timeout: u64,
+    #[fx_plus(inherit = name_prefix)]
+    #[fieldx(get(clone))]
+    prefix:  String,
+}
+
+#[fx_plus(child(Service, rc_strong, typestate_builder))]
+struct Monitor {
+    #[fx_plus(inherit = timeout)]
+    #[fieldx(get(copy))]
+    timeout: u32,
+}
+
+// Builder arguments of an inherited field apply to its setter.
+#[fx_plus(child(Service, unwrap), builder(prefix("with_")))]
+struct Probe {
+    /// Name of the probe.
+    #[fx_plus(inherit = name_prefix)]
+    #[fieldx(get(clone), builder(into))]
+    name: String,
+}
+
+#[fx_plus(child(Service, unwrap, check_alive))]
+struct Checked {
+    #[fx_plus(inherit = timeout)]
+    #[fieldx(get(copy))]
+    timeout: u32,
+}
+
+#[test]
+fn inherited_from_parent() {
+    let app = App::new();
+    let service: Rc<Service> = agent_build!(app, Service).unwrap();
+    assert_eq!(service.log_level(), 3);
+    assert_eq!(service.prefix(), "svc");
+    let quiet: Rc<Service> = agent_build!(app, Service { log_level: 0 }).unwrap();
+    assert_eq!(quiet.log_level(), 0);
+
+    let worker = child_build!(service, Worker { id: 1 }).unwrap();
+    assert_eq!(worker.timeout(), 30);
+    assert_eq!(worker.prefix(), "svc");
+
+    // Values given by the caller win.
+    let worker = child_build!(service, Worker { id: 2, timeout: 5 }).unwrap();
+    assert_eq!(worker.timeout(), 5);
+    assert_eq!(worker.prefix(), "svc");
+
+    let worker = Worker::builder()
+        .prefix("own".to_string())
+        .id(3)
+        .parent(Rc::downgrade(&service))
+        .build()
+        .unwrap();
+    assert_eq!(worker.id(), 3);
+    assert_eq!(worker.timeout(), 30);
+    assert_eq!(worker.prefix(), "own");
+
+    let base = child_builder!(
+        service,
+        Worker {
+            id:     4,
+            prefix: "base".to_string(),
+        }
+    );
+    let worker = child_build!(service, Worker { id: 5, ..base }).unwrap();
+    assert_eq!(worker.prefix(), "base");
+
+    let monitor = Monitor::typestate_builder().parent(service.clone()).build().unwrap();
+    assert_eq!(monitor.timeout(), 30);
+    let monitor = Monitor::typestate_builder().timeout(7).parent(service).build().unwrap();
+    assert_eq!(monitor.timeout(), 7);
+}
+
+#[test]
+fn inherited_checked() {
+    let app = App::new();
+    let service: Rc<Service> = agent_build!(app, Service).unwrap();
+    let checked = child_build!(service, Checked).unwrap();
+    assert_eq!(checked.timeout(), 30);
+}
+
+#[test]
+fn inherited_setter_arguments() {
+    let app = App::new();
+    let service: Rc<Service> = agent_build!(app, Service).unwrap();
+
+    let probe = Probe::builder().with_parent(Rc::downgrade(&service)).build().unwrap();
+    assert_eq!(probe.name(), "svc");
+    let probe = Probe::builder()
+        .with_name("own")
+        .with_parent(Rc::downgrade(&service))
+        .build()
+        .unwrap();
+    assert_eq!(probe.name(), "own");
+}
+
+#[test]
+fn inherited_from_gone_parent() {
+    let app = App::new();
+    let service: Rc<Service> = agent_build!(app, Service).unwrap();
+    let parent = Rc::downgrade(&service);
+    drop(service);
+
+    // Nothing to inherit from, the field default is kept.
+    let worker = Worker::builder().id(1).parent(parent.clone()).build().unwrap();
+    assert_eq!(worker.timeout(), 0);
+
+    assert!(matches!(
+        Checked::builder().parent(parent).build(),
+        Err(BuildError::ParentGoneAtBuild(_))
+    ));
+}
+
+#[cfg(feature = "sync")]
+mod with_sync {
+    use super::*;
+    use std::sync::Arc;
+
+    #[fx_plus(app, sync)]
+    struct SyncApp {
+        #[fieldx(get(copy), default(10))]
+        retries: u16,
+    }
+
+    #[fx_plus(agent(SyncApp, unwrap), sync)]
+    struct SyncAgent {
+        #[fx_plus(inherit = retries)]
+        #[fieldx(get(copy))]
+        retries: u32,
+    }
+
+    #[test]
+    fn inherited_sync() {
+        let app = SyncApp::new();
+        let agent: SyncAgent = agent_build!(app, SyncAgent).unwrap();
+        assert_eq!(agent.retries(), 10);
+        let agent: SyncAgent = agent_build!(app, SyncAgent { retries: 2 }).unwrap();
+        assert_eq!(agent.retries(), 2);
+        assert!(Arc::ptr_eq(&agent.app(), &app));
+    }
+}
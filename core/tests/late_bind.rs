@@ -0,0 +1,47 @@
+use fieldx_plus::fx_plus;
+use std::rc::Rc;
+
+#[fx_plus(app, sync(off))]
+struct MyApp {
+    #[fieldx(get(copy), default(42))]
+    answer: u32,
+}
+
+#[fx_plus(agent(MyApp, late_bind, unwrap(error)), parent, sync(off), scoped)]
+struct EarlyAgent {}
+
+#[fx_plus(child(EarlyAgent, late_bind), sync(off))]
+struct EarlyChild {}
+
+#[test]
+fn bind_later() {
+    let agent = EarlyAgent::builder().build().unwrap();
+
+    assert!(agent.app().is_err(), "the app is not bound yet");
+    assert!(!agent.app_downgrade().is_alive());
+
+    let app = MyApp::new();
+    agent.bind_app(&app);
+
+    assert_eq!(agent.with_app(|app| app.answer()), Ok(42));
+    assert!(Rc::ptr_eq(&agent.app().unwrap(), &app));
+
+    drop(app);
+
+    assert!(agent.app().is_err());
+}
+
+#[test]
+fn late_child() {
+    let agent = EarlyAgent::builder().build().unwrap();
+    let child = EarlyChild::builder().build().unwrap();
+    let other = EarlyChild::builder().build().unwrap();
+
+    assert!(child.parent().is_none());
+
+    child.bind_parent(&agent);
+    other.bind_parent(&agent);
+
+    assert!(child.same_parent(&other));
+    assert!(child.is_child_of(&agent));
+}
@@ -0,0 +1,86 @@
+use fieldx_plus::fx_plus;
+use fieldx_plus::Agent;
+use fieldx_plus::Child;
+use std::rc::Rc;
+
+#[derive(Debug)]
+struct AppError(String);
+
+impl From<fieldx::error::FieldXError> for AppError {
+    fn from(err: fieldx::error::FieldXError) -> Self {
+        Self(err.to_string())
+    }
+}
+
+#[fx_plus(app)]
+struct App {
+    #[fieldx(get(copy), default(3))]
+    retries: u8,
+    #[fx_plus(agent(port = 4242, retries = self.retries()))]
+    #[fieldx(lazy, get(clone))]
+    service: NetService,
+    #[fx_plus(agent(port = 8080))]
+    #[fieldx(lazy, get(clone))]
+    pool:    Rc<Pool>,
+    // `NetService` is not reference-counted, the built object is moved into an `Rc`.
+    #[fx_plus(agent(port = 9090))]
+    #[fieldx(lazy, get(clone))]
+    shared:  Rc<NetService>,
+    // `port` is not set, the build error is propagated.
+    #[fx_plus(agent)]
+    #[fieldx(lazy, fallible(error(AppError)), get(clone))]
+    broken:  NetService,
+}
+
+#[fx_plus(agent(App, unwrap))]
+#[derive(Clone)]
+struct NetService {
+    #[fieldx(get(copy))]
+    port:    u16,
+    #[fieldx(get(copy), default(1))]
+    retries: u8,
+}
+
+#[fx_plus(agent(App, unwrap), parent)]
+struct Pool {
+    #[fieldx(get(copy))]
+    port:   u16,
+    #[fx_plus(child(id = 1, expect("no worker")))]
+    #[fieldx(lazy, get(clone))]
+    worker: Worker,
+}
+
+#[fx_plus(child(Pool, unwrap))]
+#[derive(Clone)]
+struct Worker {
+    #[fieldx(get(copy))]
+    id: u32,
+}
+
+#[test]
+fn lazy_agents_and_children() {
+    let app = App::new();
+
+    let service = app.service();
+    assert_eq!(service.port(), 4242);
+    assert_eq!(service.retries(), 3);
+    assert!(Rc::ptr_eq(&service.app(), &app));
+
+    let shared = app.shared();
+    assert_eq!(shared.port(), 9090);
+    assert!(Rc::ptr_eq(&shared, &app.shared()));
+
+    let pool = app.pool();
+    assert_eq!(pool.port(), 8080);
+    assert!(Rc::ptr_eq(&pool, &app.pool()));
+
+    let worker = pool.worker();
+    assert_eq!(worker.id(), 1);
+    assert!(Rc::ptr_eq(&worker.parent(), &pool));
+
+    let Err(AppError(message)) = app.broken()
+    else {
+        panic!("an agent with a required field missing must not be built");
+    };
+    assert!(message.contains("port"), "unexpected error: {message}");
+}
@@ -0,0 +1,56 @@
+#![cfg(debug_assertions)]
+use fieldx_plus::agent_build;
+use fieldx_plus::fx_plus;
+use fieldx_plus::leaks::alive_apps;
+use fieldx_plus::leaks::LeakGuard;
+use std::any::Any;
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[fx_plus(app, track_leaks, sync(off))]
+struct LeakyApp {
+    // Trait objects are out of reach of the compile-time check.
+    #[fieldx(inner_mut, get_mut)]
+    keep: Vec<Rc<dyn Any>>,
+}
+
+#[fx_plus(app, track_leaks, sync(off))]
+struct CleanApp {}
+
+// Not tracked.
+#[fx_plus(app, sync(off))]
+struct QuietApp {}
+
+#[fx_plus(agent(LeakyApp, rc_strong), sync(off))]
+struct StrongAgent {}
+
+thread_local! {
+    static REPORTED: Cell<usize> = const { Cell::new(0) };
+}
+
+#[test]
+fn leaked_app() {
+    let guard = LeakGuard::new(|leaked| REPORTED.with(|r| r.set(leaked.len())));
+
+    {
+        let clean = CleanApp::new();
+        assert!(alive_apps().iter().any(|name| name.ends_with("CleanApp")));
+        drop(clean);
+    }
+    assert!(alive_apps().is_empty());
+
+    let quiet = QuietApp::new();
+    assert!(alive_apps().is_empty());
+    drop(quiet);
+
+    {
+        let app = LeakyApp::new();
+        let agent = agent_build!(app, StrongAgent).unwrap();
+        app.keep_mut().push(Rc::new(agent));
+    }
+    assert_eq!(alive_apps().len(), 1);
+    assert!(alive_apps()[0].ends_with("LeakyApp"));
+
+    drop(guard);
+    assert_eq!(REPORTED.with(|r| r.get()), 1);
+}
@@ -0,0 +1,84 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::child_build;
+use fieldx_plus::error::Role;
+use fieldx_plus::fx_plus;
+use fieldx_plus::ParentGone;
+
+#[fx_plus(app, sync(off))]
+struct MyApp {
+    #[fieldx(get(copy), default(42))]
+    answer: u32,
+}
+
+#[fx_plus(agent(MyApp, unwrap(error)), parent, sync(off), scoped)]
+struct MyAgent {}
+
+#[fx_plus(child(MyAgent, unwrap(error)), sync(off))]
+struct MyChild {}
+
+#[test]
+fn app_gone() {
+    let app = MyApp::new();
+    let agent = agent_build!(app, MyAgent).unwrap();
+
+    assert_eq!(agent.app().map(|app| app.answer()), Ok(42));
+    assert_eq!(agent.with_app(|app| app.answer()), Ok(42));
+
+    drop(app);
+
+    let err = agent.app().err().expect("the app is dropped");
+    assert_eq!(err.role(), Role::App);
+    assert_eq!(err.child_type(), "parent_gone::MyAgent");
+    assert_eq!(err.parent_type(), "parent_gone::MyApp");
+    assert_eq!(
+        err.to_string(),
+        "application object `parent_gone::MyApp` of `parent_gone::MyAgent` is gone"
+    );
+    assert_eq!(agent.with_app(|app| app.answer()), Err(err));
+}
+
+#[test]
+fn parent_gone() {
+    let app = MyApp::new();
+    let agent = agent_build!(app, MyAgent).unwrap();
+    let child = child_build!(agent, MyChild).unwrap();
+
+    assert!(child.parent().is_ok());
+
+    drop(agent);
+
+    assert_eq!(child.parent().err(), Some(ParentGone::new::<MyChild, MyAgent>()));
+    assert_eq!(
+        child.parent().err().unwrap().to_string(),
+        "parent object `parent_gone::MyAgent` of `parent_gone::MyChild` is gone"
+    );
+}
+
+#[derive(Debug, PartialEq)]
+enum MyError {
+    Gone(ParentGone),
+}
+
+impl From<ParentGone> for MyError {
+    fn from(err: ParentGone) -> Self {
+        Self::Gone(err)
+    }
+}
+
+#[fx_plus(agent(MyApp, unwrap(into(MyError))), sync(off), scoped)]
+struct IntoAgent {}
+
+#[test]
+fn into_error() {
+    let app = MyApp::new();
+    let agent = agent_build!(app, IntoAgent).unwrap();
+
+    assert_eq!(agent.with_app(|app| app.answer()), Ok(42));
+
+    drop(app);
+
+    assert_eq!(
+        agent.app().err(),
+        Some(MyError::Gone(ParentGone::for_role::<IntoAgent, MyApp>(Role::App)))
+    );
+}
@@ -0,0 +1,50 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::child_build;
+use fieldx_plus::fx_plus;
+use fieldx_plus::Agent;
+use fieldx_plus::Child;
+use fieldx_plus::Parent;
+use std::rc::Rc;
+
+#[fx_plus(app)]
+struct Server {
+    #[fieldx(get(copy), default(8080))]
+    port: u16,
+}
+
+#[fx_plus(agent(Server as server, unwrap), parent)]
+struct Listener {}
+
+#[fx_plus(child(Listener as listener, unwrap))]
+struct Connection {
+    id: u32,
+}
+
+// The parent setter gets the builder prefix like any other setter.
+#[fx_plus(child(Listener as listener, unwrap), builder(prefix("set_")))]
+struct Session {
+    #[fieldx(get(copy), default(1))]
+    id: u32,
+}
+
+#[test]
+fn renamed_relationships() {
+    let server: Rc<Server> = Server::new();
+    let listener: Rc<Listener> = agent_build!(server, Listener).unwrap();
+    let conn = child_build!(listener, Connection { id: 1 }).unwrap();
+
+    assert_eq!(listener.server().port(), 8080);
+    assert_eq!(conn.listener().server().port(), 8080);
+    assert_eq!(conn.id, 1);
+
+    // The trait methods keep their names.
+    assert_eq!(Agent::app(&*listener).port(), 8080);
+    assert!(Rc::ptr_eq(&Child::parent(&conn), &listener));
+
+    let session = child_build!(listener, Session).unwrap();
+    assert_eq!(session.id(), 1);
+    assert!(Rc::ptr_eq(&session.listener(), &listener));
+    let session = listener.child_builder::<Session>().set_id(2).build().unwrap();
+    assert_eq!(session.id(), 2);
+    assert!(Rc::ptr_eq(&session.listener(), &listener));
+}
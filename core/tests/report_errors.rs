@@ -0,0 +1,75 @@
+#![cfg(any(feature = "anyhow", feature = "eyre"))]
+use fieldx_plus::agent_build;
+use fieldx_plus::fx_plus;
+use fieldx_plus::ParentGone;
+
+#[fx_plus(app, sync(off))]
+struct MyApp {
+    #[fieldx(get(copy), default(42))]
+    answer: u32,
+}
+
+#[cfg(feature = "anyhow")]
+mod with_anyhow {
+    use super::*;
+
+    #[fx_plus(agent(MyApp, unwrap(anyhow)), sync(off))]
+    struct MyAgent {}
+
+    impl MyAgent {
+        fn answer(&self) -> anyhow::Result<u32> {
+            Ok(self.app()?.answer())
+        }
+    }
+
+    #[test]
+    fn app_gone() {
+        let app = MyApp::new();
+        let agent = agent_build!(app, MyAgent).unwrap();
+
+        assert_eq!(agent.answer().unwrap(), 42);
+
+        drop(app);
+
+        let err = agent.answer().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "application object `report_errors::MyApp` of `report_errors::with_anyhow::MyAgent` is gone"
+        );
+        assert_eq!(
+            err.downcast_ref::<ParentGone>().map(|e| e.role()),
+            Some(fieldx_plus::error::Role::App)
+        );
+    }
+}
+
+#[cfg(feature = "eyre")]
+mod with_eyre {
+    use super::*;
+
+    #[fx_plus(agent(MyApp, unwrap(eyre)), sync(off))]
+    struct MyAgent {}
+
+    impl MyAgent {
+        fn answer(&self) -> eyre::Result<u32> {
+            Ok(self.app()?.answer())
+        }
+    }
+
+    #[test]
+    fn app_gone() {
+        let app = MyApp::new();
+        let agent = agent_build!(app, MyAgent).unwrap();
+
+        assert_eq!(agent.answer().unwrap(), 42);
+
+        drop(app);
+
+        let err = agent.answer().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "application object `report_errors::MyApp` of `report_errors::with_eyre::MyAgent` is gone"
+        );
+        assert!(err.downcast_ref::<ParentGone>().is_some());
+    }
+}
@@ -0,0 +1,162 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::child_build;
+use fieldx_plus::fx_plus;
+use std::rc::Rc;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+enum MyError {
+    #[error("The app object is gone!")]
+    AppGone,
+}
+
+#[fx_plus(app, sync(off))]
+struct MyApp {
+    #[fieldx(get(copy), default(42))]
+    answer: u32,
+}
+
+#[fx_plus(agent(MyApp, unwrap(or(MyError, MyError::AppGone))), parent, sync(off), scoped)]
+struct FallibleAgent {}
+
+#[fx_plus(agent(MyApp), sync(off), scoped)]
+struct OptionalAgent {}
+
+// Without `scoped` the user is free to have their own `with_app`.
+#[fx_plus(agent(MyApp, unwrap), sync(off))]
+struct UnscopedAgent {}
+
+impl UnscopedAgent {
+    fn with_app(&self) -> u32 {
+        self.app().answer()
+    }
+}
+
+#[fx_plus(child(FallibleAgent, unwrap), sync(off), scoped)]
+struct PlainChild {}
+
+#[fx_plus(child(FallibleAgent, rc_strong), sync(off), scoped)]
+struct StrongChild {}
+
+#[test]
+fn with_app() {
+    let app = MyApp::new();
+    let fallible = agent_build!(app, FallibleAgent).unwrap();
+    let optional = agent_build!(app, OptionalAgent).unwrap();
+
+    assert_eq!(fallible.with_app(|app| app.answer()), Ok(42));
+    assert_eq!(optional.with_app(|app| app.answer() + 1), Some(43));
+    assert_eq!(Rc::strong_count(&app), 1, "no strong references are kept by the agents");
+
+    drop(app);
+
+    assert_eq!(fallible.with_app(|app| app.answer()), Err(MyError::AppGone));
+    assert_eq!(optional.with_app(|app| app.answer()), None);
+}
+
+#[test]
+fn unscoped() {
+    let app = MyApp::new();
+    let agent = agent_build!(app, UnscopedAgent).unwrap();
+
+    assert_eq!(agent.with_app(), 42);
+}
+
+#[test]
+fn with_parent() {
+    let app = MyApp::new();
+    let agent = agent_build!(app, FallibleAgent).unwrap();
+    let child = child_build!(agent, PlainChild).unwrap();
+    let strong = child_build!(agent, StrongChild).unwrap();
+
+    assert_eq!(child.with_parent(|agent| agent.with_app(|app| app.answer())), Ok(42));
+    assert_eq!(Rc::strong_count(&agent), 2, "only the rc_strong child holds the agent");
+
+    drop(agent);
+
+    assert!(strong.with_parent(|agent| agent.with_app(|app| app.answer())).is_ok());
+}
+
+#[cfg(feature = "async")]
+mod async_scoped {
+    use fieldx_plus::agent_build;
+    use fieldx_plus::fx_plus;
+    use std::sync::Arc;
+
+    #[derive(Debug, PartialEq)]
+    enum MyError {
+        AppGone,
+    }
+
+    #[fx_plus(app, r#async)]
+    struct MyApp {
+        #[fieldx(lazy, get(clone))]
+        foo: String,
+    }
+
+    impl MyApp {
+        async fn build_foo(&self) -> String {
+            "some str".to_string()
+        }
+    }
+
+    #[fx_plus(agent(MyApp, rc_strong), r#async, scoped)]
+    struct AChild {}
+
+    #[fx_plus(agent(MyApp), r#async, scoped)]
+    struct OptionalAgent {}
+
+    #[fx_plus(agent(MyApp, unwrap(or(MyError, MyError::AppGone))), r#async, scoped)]
+    struct FallibleAgent {}
+
+    #[tokio::test]
+    async fn with_app_async() {
+        let app: Arc<MyApp> = MyApp::new();
+        let ac = agent_build!(app, AChild).unwrap();
+
+        assert_eq!(
+            ac.with_app_async(|app| Box::pin(async move { app.foo().await })).await,
+            "some str".to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn with_app_async_weak() {
+        let app: Arc<MyApp> = MyApp::new();
+        let optional = agent_build!(app, OptionalAgent).unwrap();
+        let fallible = agent_build!(app, FallibleAgent).unwrap();
+
+        assert_eq!(
+            optional
+                .with_app_async(|app| Box::pin(async move { app.foo().await }))
+                .await,
+            Some("some str".to_string())
+        );
+        assert_eq!(
+            fallible
+                .with_app_async(|app| Box::pin(async move { app.foo().await.len() }))
+                .await,
+            Ok(8)
+        );
+        assert_eq!(
+            Arc::strong_count(&app),
+            1,
+            "no strong references are kept after the call"
+        );
+
+        drop(app);
+
+        assert_eq!(
+            optional
+                .with_app_async(|app| Box::pin(async move { app.foo().await }))
+                .await,
+            None
+        );
+        assert_eq!(
+            fallible
+                .with_app_async(|app| Box::pin(async move { app.foo().await }))
+                .await,
+            Err(MyError::AppGone)
+        );
+    }
+}
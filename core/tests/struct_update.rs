@@ -0,0 +1,79 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::agent_builder;
+use fieldx_plus::child_builder;
+use fieldx_plus::fx_plus;
+use std::rc::Rc;
+
+#[fx_plus(app)]
+struct Pool {}
+
+#[fx_plus(agent(Pool, unwrap), parent, builder(attributes(derive(Clone))))]
+struct Worker {
+    #[fieldx(get(copy))]
+    id:      u32,
+    #[fieldx(get(copy))]
+    timeout: u32,
+    #[fieldx(get(clone), into)]
+    name:    String,
+}
+
+#[fx_plus(child(Worker, unwrap))]
+struct Task {
+    #[fieldx(get(copy))]
+    priority: u8,
+}
+
+fn worker_defaults() -> WorkerBuilder {
+    Worker::builder().id(0).timeout(30).name("worker")
+}
+
+#[test]
+fn struct_update() {
+    let pool: Rc<Pool> = Pool::new();
+
+    let worker = agent_build!(
+        pool,
+        Worker {
+            id: 3,
+            ..worker_defaults()
+        }
+    )
+    .unwrap();
+    assert_eq!(worker.id(), 3);
+    assert_eq!(worker.timeout(), 30);
+    assert_eq!(worker.name(), "worker");
+    assert!(Rc::ptr_eq(&worker.app(), &pool));
+
+    let template = worker_defaults().timeout(10);
+    let worker2 = agent_build!(
+        pool,
+        Worker {
+            name: "second".to_string(),
+            ..template.clone()
+        }
+    )
+    .unwrap();
+    assert_eq!(worker2.id(), 0);
+    assert_eq!(worker2.timeout(), 10);
+    assert_eq!(worker2.name(), "second");
+
+    let worker3: Rc<Worker> = agent_build!(pool, Worker { ..template }).unwrap();
+    assert_eq!(worker3.timeout(), 10);
+
+    // The base builder's parent, if any, is overridden.
+    let other_pool: Rc<Pool> = Pool::new();
+    let base = agent_builder!(other_pool, Worker { ..worker_defaults() });
+    let worker4: Rc<Worker> = agent_build!(pool, Worker { ..base }).unwrap();
+    assert!(Rc::ptr_eq(&worker4.app(), &pool));
+
+    let task = child_builder!(
+        worker3,
+        Task {
+            ..Task::builder().priority(5)
+        }
+    )
+    .build()
+    .unwrap();
+    assert_eq!(task.priority(), 5);
+    assert!(Rc::ptr_eq(&task.parent(), &worker3));
+}
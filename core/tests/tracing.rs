@@ -0,0 +1,139 @@
+#![cfg(feature = "tracing")]
+use fieldx_plus::agent_build;
+use fieldx_plus::child_build;
+use fieldx_plus::fx_plus;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span;
+use tracing::Event;
+use tracing::Level;
+use tracing::Metadata;
+use tracing::Subscriber;
+
+type Record = (Level, BTreeMap<String, String>);
+
+#[derive(Default, Clone)]
+struct Collector {
+    events: Arc<Mutex<Vec<Record>>>,
+}
+
+struct FieldVisitor<'a>(&'a mut BTreeMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name().to_string(), format!("{value:?}"));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = BTreeMap::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        fields.insert("target".to_string(), event.metadata().target().to_string());
+        self.events.lock().unwrap().push((*event.metadata().level(), fields));
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+#[derive(Debug, PartialEq)]
+enum AppError {
+    AppGone,
+}
+
+#[fx_plus(app, sync(off))]
+struct MyApp {}
+
+// The line reported by the trace events of `MyAgent`.
+const AGENT_LINE: u32 = line!() + 2;
+#[fx_plus(
+    agent(MyApp, unwrap(or(AppError, AppError::AppGone), trace(warn))),
+    parent,
+    sync(off)
+)]
+struct MyAgent {}
+
+#[fx_plus(
+    child(MyAgent, unwrap(expect("The agent is gone"), also_try, trace(error))),
+    sync(off)
+)]
+struct MyChild {}
+
+#[fx_plus(agent(MyApp, unwrap(or(AppError, AppError::AppGone))), sync(off))]
+struct SilentAgent {}
+
+fn collect<F: FnOnce()>(f: F) -> Vec<Record> {
+    let collector = Collector::default();
+    tracing::subscriber::with_default(collector.clone(), f);
+    let events = collector.events.lock().unwrap().clone();
+    events
+}
+
+#[test]
+fn app_gone() {
+    let events = collect(|| {
+        let app = MyApp::new();
+        let agent = agent_build!(app, MyAgent).unwrap();
+        let silent = agent_build!(app, SilentAgent).unwrap();
+
+        assert!(agent.app().is_ok());
+        drop(app);
+        assert_eq!(agent.app().err(), Some(AppError::AppGone));
+        assert_eq!(silent.app().err(), Some(AppError::AppGone));
+    });
+
+    assert_eq!(events.len(), 1, "only a failed upgrade of a traced agent is reported");
+    let (level, fields) = &events[0];
+    assert_eq!(*level, Level::WARN);
+    assert_eq!(fields["target"], "fieldx_plus");
+    assert_eq!(fields["child"], "tracing::MyAgent");
+    assert_eq!(fields["parent"], "tracing::MyApp");
+    assert_eq!(fields["role"], "application");
+    assert!(fields["file"].ends_with("tracing.rs"));
+    assert_eq!(
+        fields["line"],
+        AGENT_LINE.to_string(),
+        "the line of the fx_plus attribute"
+    );
+}
+
+#[test]
+fn parent_gone() {
+    let events = collect(|| {
+        let app = MyApp::new();
+        let agent = agent_build!(app, MyAgent).unwrap();
+        let child = child_build!(agent, MyChild).unwrap();
+
+        drop(agent);
+        // try_parent() doesn't report since the parent is expected to be possibly gone.
+        assert!(child.try_parent().is_none());
+        assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| child.parent())).is_err());
+    });
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].0, Level::ERROR);
+    assert_eq!(events[0].1["role"], "parent");
+    assert_eq!(events[0].1["child"], "tracing::MyChild");
+}
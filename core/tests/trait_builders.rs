@@ -0,0 +1,69 @@
+use fieldx_plus::fx_plus;
+use fieldx_plus::Agent;
+use fieldx_plus::Application;
+use fieldx_plus::Child;
+use fieldx_plus::Parent;
+use std::rc::Rc;
+
+#[fx_plus(app)]
+struct MyApp {}
+
+#[fx_plus(agent(MyApp, unwrap), parent)]
+struct Service {
+    #[fieldx(get(copy))]
+    port: u16,
+}
+
+#[fx_plus(child(Service as service, unwrap))]
+struct Handler {
+    #[fieldx(get(copy))]
+    id: u32,
+}
+
+// Both an agent of the app and a child of a service.
+#[fx_plus(agent(MyApp, unwrap), child(Service, unwrap))]
+struct Monitor {}
+
+fn build_children<P, C>(parent: &P, count: usize) -> Vec<C::Builder>
+where
+    P: Parent,
+    C: Child,
+    C::WeakParent: From<P::WeakSelf>,
+{
+    (0..count).map(|_| parent.child_builder::<C>()).collect()
+}
+
+#[test]
+fn trait_builders() {
+    let app: Rc<MyApp> = MyApp::new();
+    let service: Rc<Service> = app.agent_builder::<Service>().port(8080).build().unwrap();
+    assert_eq!(service.port(), 8080);
+    assert!(Rc::ptr_eq(&service.app(), &app));
+
+    let handler = service.child_builder::<Handler>().id(1).build().unwrap();
+    assert!(Rc::ptr_eq(&handler.service(), &service));
+
+    let handlers = build_children::<_, Handler>(&*service, 3)
+        .into_iter()
+        .enumerate()
+        .map(|(id, builder)| builder.id(id as u32).build().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(handlers.len(), 3);
+    assert_eq!(handlers[2].id(), 2);
+
+    // A struct that is both an agent and a child has two builder entry points sharing the same builder type.
+    let monitor = app
+        .agent_builder::<Monitor>()
+        .parent(Rc::downgrade(&service))
+        .build()
+        .unwrap();
+    assert!(Rc::ptr_eq(&Agent::app(&monitor), &app));
+    assert!(Rc::ptr_eq(&Child::parent(&monitor), &service));
+
+    let monitor = service
+        .child_builder::<Monitor>()
+        .app(Rc::downgrade(&app))
+        .build()
+        .unwrap();
+    assert!(Rc::ptr_eq(&Agent::app(&monitor), &app));
+}
@@ -0,0 +1,82 @@
+use fieldx_plus::fx_plus;
+use fieldx_plus::tree;
+use std::rc::Rc;
+
+#[fx_plus(app)]
+struct App {
+    #[fieldx(get(copy), default(1))]
+    version: u32,
+}
+
+#[fx_plus(agent(App, unwrap), parent)]
+struct NetService {
+    #[fieldx(get(copy))]
+    port: u16,
+}
+
+#[fx_plus(child(NetService, unwrap))]
+struct Handler {
+    #[fieldx(get(copy))]
+    id: u32,
+}
+
+#[fx_plus(agent(App, unwrap))]
+struct Logger {}
+
+#[fx_plus(agent(App, unwrap), child(App, unwrap))]
+struct Both {
+    #[fieldx(get(copy))]
+    level: u8,
+}
+
+#[test]
+fn build_tree() {
+    let id = 2;
+    let tree = tree!(App::new() => {
+        NetService { port: 4242 } => {
+            Handler { id: 1 },
+            Handler { id },
+        },
+        Logger,
+        Both { level: 3 },
+    })
+    .unwrap();
+
+    let app: &Rc<App> = &tree.node;
+    let service = &tree.children.0.node;
+    assert_eq!(service.port(), 4242);
+    assert!(Rc::ptr_eq(&service.app(), app));
+
+    let (first, second) = &tree.children.0.children;
+    assert_eq!(first.node.id(), 1);
+    assert_eq!(second.node.id(), 2);
+    assert!(Rc::ptr_eq(&second.node.parent(), service));
+    assert_eq!(second.node.parent().app().version(), 1);
+
+    assert!(Rc::ptr_eq(&tree.children.1.node.app(), app));
+    assert_eq!(tree.children.2.node.level(), 3);
+    assert!(Rc::ptr_eq(&fieldx_plus::Child::parent(&tree.children.2.node), app));
+}
+
+#[test]
+fn tree_build_error() {
+    // `port` is required.
+    let result = tree!(App::new() => { NetService => { Handler { id: 1 } } });
+    assert!(result.is_err());
+
+    let root_only = tree!(App::new()).unwrap();
+    assert_eq!(root_only.into_node().version(), 1);
+}
+
+// `?` in a field expression returns from the function, not from the tree construction.
+fn service_port(port: &str) -> Result<u16, std::num::ParseIntError> {
+    let tree = tree!(App::new() => { NetService { port: port.parse()? } }).expect("the tree is built");
+    let (service,) = tree.children;
+    Ok(service.port())
+}
+
+#[test]
+fn tree_field_expressions() {
+    assert_eq!(service_port("80"), Ok(80));
+    assert!(service_port("eighty").is_err());
+}
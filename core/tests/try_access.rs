@@ -0,0 +1,64 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::child_build;
+use fieldx_plus::fx_plus;
+use fieldx_plus::Agent;
+use fieldx_plus::Child;
+use std::rc::Rc;
+
+#[fx_plus(app, sync(off))]
+struct MyApp {
+    #[fieldx(get(copy), default(42))]
+    answer: u32,
+}
+
+#[fx_plus(agent(MyApp, unwrap(expect("The app is gone"), also_try)), parent, sync(off))]
+struct MyAgent {}
+
+#[fx_plus(child(MyAgent, unwrap(also_try)), sync(off))]
+struct TryChild {}
+
+#[fx_plus(child(MyAgent, rc_strong), sync(off))]
+struct StrongChild {}
+
+fn app_answer<A: Agent<WeakApp = fieldx_plus::AppRef<std::rc::Weak<MyApp>>>>(agent: &A) -> Option<u32> {
+    agent.try_app().map(|app| app.answer())
+}
+
+#[test]
+fn try_app() {
+    let app = MyApp::new();
+    let agent = agent_build!(app, MyAgent).unwrap();
+
+    assert_eq!(agent.app().answer(), 42);
+    assert_eq!(agent.try_app().map(|app| app.answer()), Some(42));
+    assert_eq!(app_answer(agent.as_ref()), Some(42));
+
+    drop(app);
+
+    assert!(agent.try_app().is_none());
+    assert_eq!(app_answer(agent.as_ref()), None);
+}
+
+#[test]
+fn try_parent() {
+    let app = MyApp::new();
+    let agent = agent_build!(app, MyAgent).unwrap();
+    let child = child_build!(agent, TryChild).unwrap();
+    let strong = child_build!(agent, StrongChild).unwrap();
+
+    assert!(Rc::ptr_eq(&child.try_parent().unwrap(), &agent));
+
+    drop(agent);
+
+    // The trait method is available without `also_try`.
+    assert!(strong.try_parent().is_some());
+    assert!(
+        child.try_parent().is_some(),
+        "the rc_strong child keeps the agent alive"
+    );
+
+    drop(strong);
+
+    assert!(child.try_parent().is_none());
+    assert!(Child::try_parent(&child).is_none());
+}
@@ -0,0 +1,128 @@
+use fieldx_plus::agent_build;
+use fieldx_plus::child_build;
+use fieldx_plus::child_builder;
+use fieldx_plus::children_build;
+use fieldx_plus::fx_plus;
+use fieldx_plus::tree;
+use fieldx_plus::Agent;
+use fieldx_plus::Application;
+use fieldx_plus::Child;
+use fieldx_plus::Parent;
+use std::fmt::Display;
+use std::rc::Rc;
+
+#[fx_plus(app)]
+struct App {}
+
+#[fx_plus(agent(App, unwrap, typestate_builder), parent)]
+struct Service {
+    #[fieldx(get(copy), default(80))]
+    port: u16,
+}
+
+#[fx_plus(child(Service as service, unwrap, typestate_builder))]
+struct Worker<T: Display + 'static> {
+    #[fieldx(get(copy))]
+    id:    u32,
+    #[fieldx(get(clone), builder(into))]
+    label: String,
+    #[fieldx(get)]
+    tag:   T,
+}
+
+// Setters follow the builder prefix and the builder opt-in rules of fieldx.
+#[fx_plus(child(Service, unwrap, typestate_builder), builder(prefix("with_"), opt_in))]
+struct Probe {
+    #[fieldx(get(copy), builder)]
+    interval: u32,
+    #[fieldx(get(copy), default(3))]
+    retries:  u8,
+}
+
+#[test]
+fn hand_written_chain() {
+    let app: Rc<App> = App::new();
+    let service = Service::typestate_builder()
+        .port(8080)
+        .app(Rc::downgrade(&app))
+        .build()
+        .unwrap();
+    assert_eq!(service.port(), 8080);
+    assert!(Rc::ptr_eq(&service.app(), &app));
+
+    // Field setters are available before and after the parent is set.
+    let worker = Worker::typestate_builder()
+        .id(1)
+        .service(Rc::downgrade(&service))
+        .label("first")
+        .tag(3.5)
+        .build()
+        .unwrap();
+    assert_eq!(worker.id(), 1);
+    assert_eq!(worker.label(), "first");
+    assert_eq!(worker.tag().to_string(), "3.5");
+    assert!(Rc::ptr_eq(&worker.service(), &service));
+}
+
+#[test]
+fn bound_by_macros() {
+    let app: Rc<App> = App::new();
+    let service: Rc<Service> = agent_build!(app, Service).unwrap();
+    assert_eq!(service.port(), 80);
+
+    let worker = child_builder!(service, Worker<char> { id: 2, tag: 'x' })
+        .label("second")
+        .build()
+        .unwrap();
+    assert_eq!(worker.label(), "second");
+
+    let worker = child_build!(service, Worker<i32> { id: 3, label: "third", tag: 1 }).unwrap();
+    assert_eq!(worker.parent().port(), 80);
+
+    let workers = children_build!(service, Worker<u8>, 0..2u8, |i| { id: i as u32, label: "batch", tag: i }).unwrap();
+    assert_eq!(workers[1].id(), 1);
+
+    let worker = service
+        .child_builder::<Worker<&str>>()
+        .id(4)
+        .label("fourth")
+        .tag("t")
+        .build()
+        .unwrap();
+    assert_eq!(*worker.tag(), "t");
+
+    let service = app.agent_builder::<Service>().port(1).build().unwrap();
+    assert_eq!(service.port(), 1);
+}
+
+#[test]
+fn bound_in_tree() {
+    let tree = tree!(App::new() => {
+        Service { port: 9000 } => {
+            Worker<i32> { id: 5, label: "tree", tag: -1 },
+        },
+    })
+    .unwrap();
+
+    let worker = &tree.children.0.children.0.node;
+    assert_eq!(worker.id(), 5);
+    assert_eq!(worker.parent().port(), 9000);
+}
+
+#[test]
+fn prefixed_setters() {
+    let app: Rc<App> = App::new();
+    let service: Rc<Service> = agent_build!(app, Service).unwrap();
+
+    let probe = Probe::typestate_builder()
+        .with_interval(10)
+        .with_parent(Rc::downgrade(&service))
+        .build()
+        .unwrap();
+    assert_eq!(probe.interval(), 10);
+    assert_eq!(probe.retries(), 3);
+    assert!(Rc::ptr_eq(&probe.parent(), &service));
+
+    let probe = service.child_builder::<Probe>().with_interval(20).build().unwrap();
+    assert_eq!(probe.interval(), 20);
+}
@@ -2,18 +2,22 @@ use crate::ctx::FXPlusCodegenCtx;
 use crate::ctx::FXPlusMacroCtx;
 use crate::traits::ProducerDescriptor;
 use crate::types::ChildArgs;
+use crate::types::FXPlusFieldArgs;
 use darling::FromMeta;
 use fieldx::fxstruct;
 use fieldx_aux::FXBool;
 use fieldx_aux::FXOrig;
+use fieldx_aux::FXProp;
 use fieldx_aux::FXPropBool;
 use fieldx_aux::FXSetState;
 use fieldx_aux::FXSpaned;
+use fieldx_aux::FXSynTuple;
 use fieldx_aux::FXSynValue;
 use fieldx_core::codegen::constructor::FXConstructor;
 use fieldx_core::codegen::constructor::FXFieldConstructor;
 use fieldx_core::codegen::constructor::FXFnConstructor;
 use fieldx_core::codegen::constructor::FXImplConstructor;
+use fieldx_core::ctx::FXFieldCtx;
 use fieldx_core::struct_receiver::args::FXStructArgs;
 use fieldx_core::struct_receiver::FXStructReceiver;
 use proc_macro2::Span;
@@ -23,6 +27,7 @@ use quote::quote;
 use quote::quote_spanned;
 use quote::ToTokens;
 use std::rc::Rc;
+use syn::parse::Parser;
 use syn::spanned::Spanned;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -31,6 +36,26 @@ enum TranslateAs {
     OrElse,
 }
 
+// How the outcome of a parent upgrade is presented to the user by the accessor method.
+#[derive(Debug, Clone)]
+enum AccessWrap {
+    // The strong reference is returned as-is.
+    Bare,
+    Option,
+    // Result with the error type.
+    Result(TokenStream),
+}
+
+impl AccessWrap {
+    fn wrap(&self, ty: TokenStream, span: Span) -> TokenStream {
+        match self {
+            Self::Bare => ty,
+            Self::Option => quote_spanned! {span=> ::std::option::Option<#ty>},
+            Self::Result(error_type) => quote_spanned! {span=> ::std::result::Result<#ty, #error_type>},
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct AppDescriptor {}
 
@@ -64,6 +89,16 @@ impl ProducerDescriptor for AppDescriptor {
     fn fxp_assoc_type(span: Span) -> syn::Ident {
         format_ident!("FXPApp", span = span)
     }
+
+    #[inline(always)]
+    fn handle_type(span: Span) -> syn::Ident {
+        format_ident!("AppRef", span = span)
+    }
+
+    #[inline(always)]
+    fn role_variant(span: Span) -> syn::Ident {
+        format_ident!("App", span = span)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -99,34 +134,57 @@ impl ProducerDescriptor for ParentDescriptor {
     fn fxp_assoc_type(span: Span) -> syn::Ident {
         format_ident!("FXPParent", span = span)
     }
+
+    #[inline(always)]
+    fn handle_type(span: Span) -> syn::Ident {
+        format_ident!("ParentRef", span = span)
+    }
+
+    #[inline(always)]
+    fn role_variant(span: Span) -> syn::Ident {
+        format_ident!("Parent", span = span)
+    }
 }
 
 #[fxstruct(get)]
 #[derive(FromMeta, Debug, Clone)]
 pub(crate) struct FXPlusArgs {
     #[fieldx(optional, get(as_ref))]
-    agent:    FXSynValue<ChildArgs<AppDescriptor>>,
+    agent:       FXSynValue<ChildArgs<AppDescriptor>>,
+    #[fieldx(optional, get(as_ref))]
+    app:         FXBool,
     #[fieldx(optional, get(as_ref))]
-    app:      FXBool,
+    parent:      FXBool,
     #[fieldx(optional, get(as_ref))]
-    parent:   FXBool,
+    child:       FXSynValue<ChildArgs<ParentDescriptor>>,
     #[fieldx(optional, get(as_ref))]
-    child:    FXSynValue<ChildArgs<ParentDescriptor>>,
+    identity:    FXBool,
+    #[fieldx(optional, get(as_ref))]
+    scoped:      FXBool,
+    #[fieldx(optional, get(as_ref))]
+    track_leaks: FXBool,
+    #[fieldx(optional, get(as_ref))]
+    build_error: FXBool,
     #[darling(flatten)]
-    std_args: FXStructArgs,
+    std_args:    FXStructArgs,
 }
 
 #[fxstruct(new(off))]
 pub(crate) struct FXPlusProducer {
-    args: FXPlusArgs,
-    ctx:  Rc<FXPlusCodegenCtx>,
+    args:       FXPlusArgs,
+    field_args: Vec<(syn::Ident, FXPlusFieldArgs)>,
+    ctx:        Rc<FXPlusCodegenCtx>,
 }
 
 impl FXPlusProducer {
-    pub fn new(args: FXPlusArgs, plus_struct: FXStructReceiver) -> Self {
+    pub fn new(
+        args: FXPlusArgs,
+        field_args: Vec<(syn::Ident, FXPlusFieldArgs)>,
+        plus_struct: FXStructReceiver,
+    ) -> Self {
         let impl_ctx = FXPlusMacroCtx::new();
         let ctx = FXPlusCodegenCtx::new(plus_struct, args.std_args.clone(), impl_ctx);
-        Self { args, ctx }
+        Self { args, field_args, ctx }
     }
 
     fn ctx(&self) -> &FXPlusCodegenCtx {
@@ -168,6 +226,22 @@ impl FXPlusProducer {
         Ok(expr)
     }
 
+    // Unlike `translate_or_expr`, produces an expression that awaits the handler.
+    fn translate_or_async_expr(&self, expr: &syn::Expr, span: Span) -> TokenStream {
+        match expr {
+            syn::Expr::Path(ref path) => {
+                if let Some(ident) = path.path.get_ident() {
+                    quote_spanned! {path.span()=> self.#ident().await}
+                }
+                else {
+                    quote_spanned! {path.span()=> #path().await}
+                }
+            }
+            syn::Expr::Closure(ref closure) => quote_spanned! {span=> (#closure)().await},
+            _ => quote_spanned! {span=> (#expr).await},
+        }
+    }
+
     fn setup_parentish_field<D: ProducerDescriptor>(&self, child_args: &ChildArgs<D>) -> darling::Result<()> {
         let ctx = self.ctx();
         let parent_type = child_args.parent_type();
@@ -183,33 +257,228 @@ impl FXPlusProducer {
             ctx.impl_details().ref_count_weak(rc_strong_span)
         };
 
-        let mut field_constructor = FXFieldConstructor::new(
-            field_ident,
-            quote_spanned! {rc_strong_span=> #rc_type<#parent_type>},
-            child_args.span(),
-        );
-
         let mut serde_off = ctx.impl_ctx().serde_off();
         if !serde_off.is_empty() {
             serde_off = quote_spanned! {child_args.span()=> , #serde_off};
         }
-        field_constructor.add_attribute_toks(quote_spanned! {child_args.span()=>
-            #[fieldx(
-                lazy(off), predicate(off), clearer(off), get(off), set(off),
-                builder(#builder_name) #serde_off
-            )]
-        })?;
+
+        let field_constructor = if child_args.is_late_bound() {
+            if let syn::Type::TraitObject(_) = parent_type {
+                return Err(
+                    darling::Error::custom("`late_bind` cannot be used with a trait object type")
+                        .with_span(parent_type),
+                );
+            }
+
+            // A late-bound parent is not known to the builder.
+            let mut field_constructor = FXFieldConstructor::new(
+                field_ident,
+                quote_spanned! {child_args.span()=> ::fieldx_plus::late::LateBind<#rc_type<#parent_type>>},
+                child_args.span(),
+            );
+            field_constructor.add_attribute_toks(quote_spanned! {child_args.span()=>
+                #[fieldx(
+                    lazy(off), predicate(off), clearer(off), get(off), set(off), builder(off),
+                    default(::fieldx_plus::late::LateBind::new()) #serde_off
+                )]
+            })?;
+            field_constructor
+        }
+        else {
+            let mut field_constructor = FXFieldConstructor::new(
+                field_ident,
+                quote_spanned! {rc_strong_span=> #rc_type<#parent_type>},
+                child_args.span(),
+            );
+            field_constructor.add_attribute_toks(quote_spanned! {child_args.span()=>
+                #[fieldx(
+                    lazy(off), predicate(off), clearer(off), get(off), set(off),
+                    builder(#builder_name) #serde_off
+                )]
+            })?;
+            field_constructor
+        };
 
         ctx.user_struct_mut().add_field(field_constructor);
 
         Ok(())
     }
 
+    // Fields declared with `inherit = getter` and the getter names.
+    fn inherited_fields(&self) -> Vec<(&syn::Ident, &syn::Ident)> {
+        self.field_args
+            .iter()
+            .filter_map(|(field_ident, field_args)| field_args.inherit().as_ref().map(|getter| (field_ident, getter)))
+            .collect()
+    }
+
+    // Fill the inherited fields when the struct is built: the value given to the builder wins, otherwise it is taken
+    // from the app/parent if it is still alive. The field default is kept if neither is available.
+    fn setup_inherited_fields<D: ProducerDescriptor>(
+        &self,
+        child_args: &ChildArgs<D>,
+        post_build: &mut Vec<TokenStream>,
+    ) -> darling::Result<()> {
+        let inherited = self.inherited_fields();
+        let Some((_, first_getter)) = inherited.first()
+        else {
+            return Ok(());
+        };
+
+        if child_args.is_late_bound() {
+            return Err(darling::Error::custom(format!(
+                "`inherit` cannot be used with a late-bound {}",
+                D::base_name()
+            ))
+            .with_span(*first_getter));
+        }
+
+        let ctx = self.ctx();
+        let args = &self.args;
+        let arg_props = ctx.arg_props();
+        if *arg_props.has_post_build() {
+            return Err(
+                darling::Error::custom("`inherit` cannot be used with custom builder `post_build`")
+                    .with_span(*first_getter),
+            );
+        }
+
+        // fieldx calls `post_build` of a reference-counted struct where it cannot fail.
+        let is_rc = arg_props.rc().or(args.app.is_set()).or(args.parent.is_set());
+        if *is_rc && (*arg_props.builder_has_error_type() || self.is_build_error()) {
+            return Err(darling::Error::custom(
+                "`inherit` cannot be used with a reference-counted struct that has a builder error type",
+            )
+            .with_span(*first_getter));
+        }
+
+        let span = child_args.span();
+        let parent_field_ident = ctx.impl_ctx().parent_field_ident(child_args);
+        let parent = if *child_args.rc_strong().is_set() {
+            quote_spanned! {span=> ::std::option::Option::Some(&self.#parent_field_ident)}
+        }
+        else {
+            let rc_weak = ctx.impl_details().ref_count_weak(span);
+            quote_spanned! {span=> #rc_weak::upgrade(&self.#parent_field_ident)}
+        };
+
+        for (field_ident, getter) in inherited {
+            let value_ident = inherited_value_ident(field_ident);
+            post_build.push(quote_spanned! {getter.span()=>
+                if let ::std::option::Option::Some(__fxplus_value) = self.#value_ident.take() {
+                    self.#field_ident = __fxplus_value;
+                }
+                else if let ::std::option::Option::Some(__fxplus_parent) = #parent {
+                    self.#field_ident = ::std::convert::Into::into(__fxplus_parent.#getter());
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    // Reference to the weak pointer to the app/parent.
+    fn parent_weak_ref<D: ProducerDescriptor>(&self, child_args: &ChildArgs<D>, span: Span) -> TokenStream {
+        let parent_field_ident = self.ctx().impl_ctx().parent_field_ident(child_args);
+        if child_args.is_late_bound() {
+            quote_spanned! {span=> &self.#parent_field_ident.get()}
+        }
+        else {
+            quote_spanned! {span=> &self.#parent_field_ident}
+        }
+    }
+
+    // Name of the fieldx builder setter for the given base name, with the builder `prefix` applied the way fieldx does.
+    fn builder_setter_ident(&self, base_ident: &syn::Ident) -> syn::Ident {
+        let prefix = self
+            .ctx()
+            .arg_props()
+            .builder_prefix()
+            .map_or(String::new(), |p| p.to_string());
+        format_ident!("{}{}", prefix, base_ident, span = base_ident.span())
+    }
+
+    // Expression constructing `ParentGone` for the current struct and its app/parent.
+    fn parent_gone_expr<D: ProducerDescriptor>(parent_type: &syn::Type, span: Span) -> TokenStream {
+        let role_variant = D::role_variant(span);
+        quote_spanned! {span=>
+            ::fieldx_plus::ParentGone::for_role::<Self, #parent_type>(::fieldx_plus::error::Role::#role_variant)
+        }
+    }
+
+    // Substitute `{child}`, `{parent}`, and `{role}` placeholders in an `expect` message. If `{self}` is used then the
+    // message is formatted at run time with `Debug` representation of the agent/child.
+    fn expand_expect<D: ProducerDescriptor>(
+        &self,
+        child_args: &ChildArgs<D>,
+        message: &str,
+        span: Span,
+    ) -> TokenStream {
+        let child = self.ctx().input().ident().to_string();
+        let parent = type_to_string(child_args.parent_type());
+        let expanded = message
+            .replace("{child}", &child)
+            .replace("{parent}", &parent)
+            .replace("{role}", D::kind());
+
+        if expanded.contains("{self}") {
+            let format = expanded
+                .split("{self}")
+                .map(|chunk| chunk.replace('{', "{{").replace('}', "}}"))
+                .collect::<Vec<_>>()
+                .join("{0:?}");
+            quote_spanned! {span=> .unwrap_or_else(|| ::std::panic!(#format, self))}
+        }
+        else {
+            quote_spanned! {span=> .expect(#expanded)}
+        }
+    }
+
+    // Option combinator reporting a failed upgrade when `trace(<level>)` is requested. Empty otherwise.
+    fn upgrade_trace_tap<D: ProducerDescriptor>(child_args: &ChildArgs<D>) -> darling::Result<TokenStream> {
+        let Some(trace) = child_args.unwrap_parent().as_ref().and_then(|u| u.trace().as_ref())
+        else {
+            return Ok(quote![]);
+        };
+
+        let level = trace.value();
+        let span = trace.final_span();
+        let variant = match level.to_string().as_str() {
+            "trace" => "Trace",
+            "debug" => "Debug",
+            "info" => "Info",
+            "warn" => "Warn",
+            "error" => "Error",
+            _ => {
+                return Err(
+                    darling::Error::custom("Expected one of `trace`, `debug`, `info`, `warn`, or `error`")
+                        .with_span(level),
+                )
+            }
+        };
+        let variant = format_ident!("{}", variant, span = span);
+        let parent_type = child_args.parent_type();
+        let role_variant = D::role_variant(span);
+        // The location is reported for the outermost macro invocation, i.e. the `fx_plus` attribute.
+        let location = quote_spanned! {child_args.span()=> ::std::file!(), ::std::line!(), ::std::column!()};
+
+        Ok(quote_spanned! {span=>
+            .or_else(|| {
+                ::fieldx_plus::trace::upgrade_failed::<Self, #parent_type>(
+                    ::fieldx_plus::trace::Level::#variant,
+                    ::fieldx_plus::error::Role::#role_variant,
+                    #location
+                );
+                ::std::option::Option::None
+            })
+        })
+    }
+
     fn setup_unwrapping<D: ProducerDescriptor>(
         &self,
         trait_constructor: &mut FXImplConstructor,
         child_args: &ChildArgs<D>,
-    ) -> darling::Result<TokenStream> {
+    ) -> darling::Result<(TokenStream, AccessWrap)> {
         let ctx = self.ctx();
         let is_rc_strong = child_args.rc_strong().is_set();
         let rc_type_span = child_args
@@ -221,10 +490,10 @@ impl FXPlusProducer {
         let rc_assoc = D::rc_assoc_type(child_args.span());
         let weak_assoc = D::weak_assoc_type(child_args.span());
         let fxp_assoc = D::fxp_assoc_type(child_args.span());
-        let mut return_type = quote![#rc_strong<#parent_type>];
+        let handle_type = D::handle_type(child_args.span());
 
         trait_constructor.add_assoc_type(quote_spanned! {parent_type.span()=>
-            type #weak_assoc = #rc_weak<#parent_type>;
+            type #weak_assoc = ::fieldx_plus::#handle_type<#rc_weak<#parent_type>>;
         });
 
         let fxp_rc_type = if *is_rc_strong { &rc_strong } else { &rc_weak };
@@ -233,18 +502,19 @@ impl FXPlusProducer {
             type #fxp_assoc = #fxp_rc_type<#parent_type>;
         });
 
-        Ok(if let Some(unwrap_arg) = child_args.unwrap_parent() {
+        let trace_tap = Self::upgrade_trace_tap(child_args)?;
+
+        let (unwrap, access_wrap) = if let Some(unwrap_arg) = child_args.unwrap_parent() {
             if let Some(expect) = unwrap_arg.expect_arg() {
                 let Some(expect_message) = expect.value()
                 else {
                     return Err(darling::Error::custom("Missing message for the 'expect' argument").with_span(&expect));
                 };
 
-                trait_constructor.add_assoc_type(quote_spanned! {parent_type.span()=>
-                    type #rc_assoc = #return_type;
-                });
-
-                quote_spanned![expect.span()=> .expect(#expect_message)]
+                (
+                    self.expand_expect::<D>(child_args, expect_message, expect.span()),
+                    AccessWrap::Bare,
+                )
             }
             else if unwrap_arg.or_arg().is_set_bool() || unwrap_arg.or_else_arg().is_set_bool() {
                 let Some(or_arg) = unwrap_arg
@@ -255,43 +525,110 @@ impl FXPlusProducer {
                     return Err(darling::Error::custom("Internal error: either `or(...)` or `or_else(...)` subarguments are reported as set, but none contains a value").with_span(&unwrap_arg.final_span()));
                 };
                 let error_type = or_arg.0.to_token_stream();
-                return_type =
-                    quote_spanned![or_arg.0.span()=> ::std::result::Result<#rc_strong<#parent_type>, #error_type>];
-
-                trait_constructor.add_assoc_type(quote_spanned! {parent_type.span()=>
-                    type #rc_assoc = #return_type;
-                });
+                let wrap = AccessWrap::Result(error_type);
 
-                if unwrap_arg.or_arg().is_set_bool() {
+                let unwrap = if unwrap_arg.or_arg().is_set_bool() {
                     let expr = self.translate_or_expr(&or_arg.1, TranslateAs::Or, or_arg.final_span())?;
                     quote_spanned![or_arg.final_span()=> .ok_or(#expr)]
                 }
                 else {
                     let expr = self.translate_or_expr(&or_arg.1, TranslateAs::OrElse, or_arg.final_span())?;
                     quote_spanned![or_arg.final_span()=> .ok_or_else(#expr)]
+                };
+
+                (unwrap, wrap)
+            }
+            else if let Some(or_else_async) = unwrap_arg.or_else_async_arg() {
+                if !ctx.arg_props().mode_async().is_some_and(|p| *p) {
+                    return Err(
+                        darling::Error::custom("`or_else_async` requires the struct to be in async mode")
+                            .with_span(&or_else_async.final_span()),
+                    );
+                }
+
+                // A trait method cannot be async. The trait implementation reports `ParentGone`, the error handler is
+                // only invoked by the async inherent accessor.
+                let span = or_else_async.final_span();
+                let wrap = AccessWrap::Result(quote_spanned! {span=> ::fieldx_plus::ParentGone});
+
+                let parent_gone = Self::parent_gone_expr::<D>(parent_type, span);
+                (quote_spanned! {span=> .ok_or_else(|| #parent_gone)}, wrap)
+            }
+            else if let Some(error_arg) = unwrap_arg.error_arg().as_ref().filter(|e| e.is_set_bool()) {
+                let span = error_arg.final_span();
+                let wrap = AccessWrap::Result(quote_spanned! {span=> ::fieldx_plus::ParentGone});
+
+                let parent_gone = Self::parent_gone_expr::<D>(parent_type, span);
+                (quote_spanned! {span=> .ok_or_else(|| #parent_gone)}, wrap)
+            }
+            else if let Some((report_crate, enabled, span)) = unwrap_arg
+                .anyhow_arg()
+                .as_ref()
+                .filter(|a| a.is_set_bool())
+                .map(|a| ("anyhow", cfg!(feature = "anyhow"), a.final_span()))
+                .or_else(|| {
+                    unwrap_arg
+                        .eyre_arg()
+                        .as_ref()
+                        .filter(|e| e.is_set_bool())
+                        .map(|e| ("eyre", cfg!(feature = "eyre"), e.final_span()))
+                })
+            {
+                if !enabled {
+                    return Err(darling::Error::custom(format!(
+                        "`unwrap({report_crate})` requires the `{report_crate}` feature of fieldx_plus"
+                    ))
+                    .with_span(&span));
                 }
+                // `ParentGone` is wrapped into the report so that it can be downcast to.
+                let report_crate = format_ident!("__{}", report_crate, span = span);
+                let error_type = quote_spanned! {span=> ::fieldx_plus::#report_crate::Error};
+                let wrap = AccessWrap::Result(error_type.clone());
+
+                let parent_gone = Self::parent_gone_expr::<D>(parent_type, span);
+                (
+                    quote_spanned! {span=> .ok_or_else(|| #error_type::new(#parent_gone))},
+                    wrap,
+                )
             }
-            else {
-                trait_constructor.add_assoc_type(quote_spanned! {parent_type.span()=>
-                    type #rc_assoc = #return_type;
-                });
+            else if let Some(convert_arg) = unwrap_arg.convert_arg() {
+                let span = convert_arg.final_span();
+                let error_type = convert_arg.value().to_token_stream();
+                let wrap = AccessWrap::Result(error_type.clone());
+
+                let parent_gone = Self::parent_gone_expr::<D>(parent_type, span);
+                (
+                    quote_spanned! {span=>
+                        .ok_or_else(|| {
+                            <#error_type as ::std::convert::From<::fieldx_plus::ParentGone>>::from(#parent_gone)
+                        })
+                    },
+                    wrap,
+                )
+            }
+            else if let Some(default_arg) = unwrap_arg.default_arg() {
+                let span = default_arg.final_span();
 
-                quote_spanned![unwrap_arg.final_span()=> .unwrap()]
+                let expr = self.translate_or_expr(default_arg.value(), TranslateAs::OrElse, span)?;
+                (quote_spanned![span=> .unwrap_or_else(#expr)], AccessWrap::Bare)
+            }
+            else {
+                (quote_spanned![unwrap_arg.final_span()=> .unwrap()], AccessWrap::Bare)
             }
         }
         else if *is_rc_strong {
-            trait_constructor.add_assoc_type(quote_spanned! {parent_type.span()=>
-                type #rc_assoc = #return_type;
-            });
-
-            quote![]
+            (quote![], AccessWrap::Bare)
         }
         else {
-            trait_constructor.add_assoc_type(quote_spanned! {parent_type.span()=>
-                type #rc_assoc = ::std::option::Option<#return_type>;
-            });
-            quote![]
-        })
+            (quote![], AccessWrap::Option)
+        };
+
+        let return_type = access_wrap.wrap(quote![#rc_strong<#parent_type>], parent_type.span());
+        trait_constructor.add_assoc_type(quote_spanned! {parent_type.span()=>
+            type #rc_assoc = #return_type;
+        });
+
+        Ok((quote! {#trace_tap #unwrap}, access_wrap))
     }
 
     fn setup_child_methods<D: ProducerDescriptor>(
@@ -303,15 +640,19 @@ impl FXPlusProducer {
         let child_args_span = child_args.span();
         let parent_base_ident = child_args.parent_base_ident();
         let parent_field_ident = ctx.impl_ctx().parent_field_ident(child_args);
+        let parent_weak = self.parent_weak_ref(child_args, child_args_span);
         let rc_assoc = D::rc_assoc_type(child_args.span());
         let weak_assoc = D::weak_assoc_type(child_args.span());
         let fxp_assoc = D::fxp_assoc_type(child_args.span());
+        let handle_type = D::handle_type(child_args_span);
         let is_rc_strong = child_args.rc_strong().is_set();
         let rc_weak_type = ctx.impl_details().ref_count_weak(child_args_span);
 
-        let final_unwrap = self.setup_unwrapping(trait_constructor, child_args)?;
+        let (final_unwrap, access_wrap) = self.setup_unwrapping(trait_constructor, child_args)?;
 
-        let mut parent_method = FXFnConstructor::new(parent_base_ident.clone());
+        // Trait methods are named after the trait, not after the possibly renamed parent.
+        let mut parent_method =
+            FXFnConstructor::new(format_ident!("{}", D::base_name(), span = parent_base_ident.span()));
         parent_method
             .set_self_borrow(true)
             .set_span(child_args_span)
@@ -319,7 +660,7 @@ impl FXPlusProducer {
 
         let mut parent_downgrade_method = FXFnConstructor::new(format_ident!(
             "{}_downgrade",
-            parent_base_ident,
+            D::base_name(),
             span = parent_base_ident.span()
         ));
         parent_downgrade_method
@@ -328,11 +669,8 @@ impl FXPlusProducer {
             .set_ret_type(quote_spanned! {child_args_span=> Self::#weak_assoc});
 
         // This method is for the use of macro_rules since its name is not dependent on the user-specified parent name.
-        let mut fxplus_parent_method = FXFnConstructor::new_associated(format_ident!(
-            "__fxplus_{}",
-            D::base_name(),
-            span = parent_base_ident.span()
-        ));
+        let fxplus_parent_ident = format_ident!("__fxplus_{}", D::base_name(), span = parent_base_ident.span());
+        let mut fxplus_parent_method = FXFnConstructor::new_associated(fxplus_parent_ident.clone());
         fxplus_parent_method
             .set_span(child_args_span)
             .set_ret_type(quote_spanned! {child_args_span=> Self::#fxp_assoc})
@@ -344,116 +682,797 @@ impl FXPlusProducer {
             parent_method
                 .set_ret_stmt(quote_spanned! {child_args_span=> #rc_strong_type::clone(&self.#parent_field_ident) });
 
-            parent_downgrade_method.set_ret_stmt(
-                quote_spanned! {child_args_span=> #rc_strong_type::downgrade(&self.#parent_field_ident) },
-            );
+            parent_downgrade_method.set_ret_stmt(quote_spanned! {child_args_span=>
+                ::fieldx_plus::#handle_type::new(#rc_strong_type::downgrade(&self.#parent_field_ident))
+            });
 
             // unwrap() is safe here because this code is part of app/parent builder macros. Its use outside the macros
             // is at the user's discretion.
             fxplus_parent_method
-                .set_ret_stmt(quote_spanned! {child_args_span=> #rc_weak_type::upgrade(&#parent_base_ident).unwrap() });
+                .set_ret_stmt(quote_spanned! {child_args_span=> #parent_base_ident.upgrade().unwrap() });
         }
         else {
-            parent_method.set_ret_stmt(
-                quote_spanned! {child_args_span=> #rc_weak_type::upgrade(&self.#parent_field_ident) #final_unwrap },
-            );
+            parent_method
+                .set_ret_stmt(quote_spanned! {child_args_span=> #rc_weak_type::upgrade(#parent_weak) #final_unwrap });
+
+            parent_downgrade_method.set_ret_stmt(quote_spanned! {child_args_span=>
+                ::fieldx_plus::#handle_type::new(#rc_weak_type::clone(#parent_weak))
+            });
+
+            fxplus_parent_method.set_ret_stmt(quote_spanned! {child_args_span=> #parent_base_ident.into_weak() });
+        }
 
-            parent_downgrade_method
-                .set_ret_stmt(quote_spanned! {child_args_span=> #rc_weak_type::clone(&self.#parent_field_ident) });
+        // The builder hook for macro_rules. The builder setter is named after the parent base ident which may be
+        // renamed with `as`.
+        let mut fxplus_bind_method = FXFnConstructor::new_associated(format_ident!(
+            "__fxplus_bind_{}",
+            D::base_name(),
+            span = parent_base_ident.span()
+        ));
+        // A struct can be both an agent and a child, hence the qualified builder type.
+        let trait_name = D::child_trait_name(child_args_span);
+        let builder_type = quote_spanned! {child_args_span=> <Self as #trait_name>::Builder};
+        let unbound_builder_type = quote_spanned! {child_args_span=> <Self as #trait_name>::UnboundBuilder};
+        fxplus_bind_method.set_span(child_args_span).set_ret_type(builder_type);
+        if child_args.is_late_bound() {
+            // A late-bound parent has no builder setter and can only be set on the built object. Instead of silently
+            // dropping it, give the builder macros a builder that cannot be built.
+            fxplus_bind_method
+                .add_param(quote_spanned! {child_args_span=> _: #unbound_builder_type})
+                .add_param(quote_spanned! {child_args_span=> _: Self::#weak_assoc})
+                .set_ret_stmt(quote_spanned! {child_args_span=> ::fieldx_plus::late::LateBindBuilder::default()});
+        }
+        else {
+            let setter_ident = self.builder_setter_ident(&parent_base_ident);
+            fxplus_bind_method
+                .add_param(quote_spanned! {child_args_span=> builder: #unbound_builder_type})
+                .add_param(quote_spanned! {child_args_span=> #parent_base_ident: Self::#weak_assoc})
+                .set_ret_stmt(quote_spanned! {child_args_span=>
+                    builder.#setter_ident(Self::#fxplus_parent_ident(#parent_base_ident))
+                });
+        }
 
-            fxplus_parent_method.set_ret_stmt(quote! {#parent_base_ident});
+        let mut fxplus_builder_method = FXFnConstructor::new_associated(format_ident!(
+            "__fxplus_{}_builder",
+            D::base_name(),
+            span = child_args_span
+        ));
+        fxplus_builder_method
+            .set_span(child_args_span)
+            .set_ret_type(unbound_builder_type);
+
+        if child_args.is_typestate_builder() {
+            let typestate_ident = self.typestate_builder_ident();
+            let bound_args =
+                self.typestate_generic_args(quote_spanned! {child_args_span=> ::fieldx_plus::typestate::Bound});
+            let unbound_args =
+                self.typestate_generic_args(quote_spanned! {child_args_span=> ::fieldx_plus::typestate::Unbound});
+            trait_constructor
+                .add_assoc_type(quote_spanned! {child_args_span=> type Builder = #typestate_ident #bound_args;})
+                .add_assoc_type(quote_spanned! {child_args_span=>
+                    type UnboundBuilder = #typestate_ident #unbound_args;
+                });
+            fxplus_builder_method.set_ret_stmt(quote_spanned! {child_args_span=> Self::typestate_builder()});
+        }
+        else {
+            let builder_ident = ctx.arg_props().builder_ident().clone();
+            let (_, ty_generics, _) = ctx.input().generics().split_for_impl();
+            let builder_type = if child_args.is_late_bound() {
+                quote_spanned! {child_args_span=> ::fieldx_plus::late::LateBindBuilder<Self>}
+            }
+            else {
+                quote_spanned! {child_args_span=> #builder_ident #ty_generics}
+            };
+            trait_constructor
+                .add_assoc_type(quote_spanned! {child_args_span=> type Builder = #builder_type;})
+                .add_assoc_type(quote_spanned! {child_args_span=>
+                    type UnboundBuilder = #builder_ident #ty_generics;
+                });
+            fxplus_builder_method.set_ret_stmt(quote_spanned! {child_args_span=> Self::builder()});
         }
 
         trait_constructor
             .add_method(parent_method)
             .add_method(parent_downgrade_method)
-            .add_method(fxplus_parent_method);
+            .add_method(fxplus_parent_method)
+            .add_method(fxplus_bind_method)
+            .add_method(fxplus_builder_method);
+
+        let async_access = if let Some(or_else_async) = child_args
+            .unwrap_parent()
+            .as_ref()
+            .and_then(|u| u.or_else_async_arg().as_ref())
+        {
+            Some(self.setup_async_accessor(child_args, or_else_async)?)
+        }
+        else {
+            None
+        };
 
-        Ok(())
-    }
+        if parent_base_ident != D::base_name() {
+            self.setup_renamed_methods(child_args)?;
+        }
 
-    fn impl_childish_trait<D: ProducerDescriptor>(&self, child_args: &ChildArgs<D>) -> darling::Result<()> {
-        let ctx = self.ctx();
-        let child_args_span = child_args.span();
-        let trait_name = D::child_trait_name(child_args_span);
-        let mut trait_constructor = FXImplConstructor::new(trait_name);
+        if *self.args.scoped.is_set() {
+            self.setup_scoped_methods(child_args, &final_unwrap, &access_wrap, async_access)?;
+        }
 
-        trait_constructor
-            .set_span(child_args_span)
-            .set_from_generics(Some(ctx.input().generics().clone()))
-            .set_for_ident(ctx.input_ident());
+        if child_args
+            .unwrap_parent()
+            .as_ref()
+            .is_some_and(|u| u.also_try().is_set_bool())
+        {
+            self.setup_try_method(child_args)?;
+        }
 
-        self.setup_child_methods(&mut trait_constructor, child_args)?;
-        ctx.impl_ctx_mut().add_trait(trait_constructor);
+        if child_args.is_late_bound() {
+            self.setup_late_bind_methods(child_args)?;
+        }
 
         Ok(())
     }
 
-    fn impl_parent_trait(&self) -> darling::Result<()> {
-        let args = &self.args;
+    // Async accessor for `or_else_async`, named `<base>_async` to not collide with the trait method. Returns the
+    // expression to obtain the parent in async context and how its outcome is wrapped.
+    fn setup_async_accessor<D: ProducerDescriptor>(
+        &self,
+        child_args: &ChildArgs<D>,
+        or_else_async: &FXSynTuple<(syn::Path, syn::Expr)>,
+    ) -> darling::Result<(TokenStream, AccessWrap)> {
         let ctx = self.ctx();
+        let span = or_else_async.final_span();
+        let parent_type = child_args.parent_type();
+        let parent_base_ident = child_args.parent_base_ident();
+        let parent_weak = self.parent_weak_ref(child_args, span);
+        let rc_strong_type = ctx.impl_details().ref_count_strong(span);
+        let rc_weak_type = ctx.impl_details().ref_count_weak(span);
+        let vis = ctx.input().vis().to_token_stream();
+        let wrap = AccessWrap::Result(or_else_async.0.to_token_stream());
+        let handler = self.translate_or_async_expr(&or_else_async.1, span);
+        let trace_tap = Self::upgrade_trace_tap(child_args)?;
+
+        let accessor_ident = format_ident!("{}_async", parent_base_ident, span = parent_base_ident.span());
+
+        let mut accessor = FXFnConstructor::new(accessor_ident.clone());
+        accessor
+            .set_self_borrow(true)
+            .set_span(span)
+            .set_vis(&vis)
+            .set_async(FXProp::new(true, Some(span)))
+            .set_ret_type(wrap.wrap(quote_spanned! {span=> #rc_strong_type<#parent_type>}, span))
+            .set_ret_stmt(quote_spanned! {span=>
+                match #rc_weak_type::upgrade(#parent_weak) #trace_tap {
+                    ::std::option::Option::Some(#parent_base_ident) => ::std::result::Result::Ok(#parent_base_ident),
+                    ::std::option::Option::None => ::std::result::Result::Err(#handler),
+                }
+            })
+            .add_attribute_toks(quote_spanned! {span=> #[allow(dead_code)]})?;
 
-        let trait_name: syn::Path = syn::parse2(quote! { ::fieldx_plus::Parent })?;
-        let weak_type = ctx.impl_details().ref_count_weak(args.parent.span());
-        let mut trait_constructor = FXImplConstructor::new(trait_name);
-
-        trait_constructor
-            .add_assoc_type(quote_spanned! {args.parent.span()=> type WeakSelf = #weak_type<Self>;})
-            .set_from_generics(Some(ctx.input().generics().clone()))
-            .set_for_ident(ctx.input_ident());
+        ctx.add_method(accessor);
 
-        // Add fx_plus downgrade method
-        let mut downgrade_method =
-            FXFnConstructor::new(format_ident!("__fxplus_myself_downgrade", span = args.parent.span()));
-        downgrade_method
-            .set_span(args.parent.span())
-            .add_attribute_toks(quote_spanned! {args.parent.span()=> #[inline(always)]})?
-            .set_ret_type(quote_spanned! {args.parent.span()=> #weak_type<Self>})
-            .set_ret_stmt(quote_spanned! {args.parent.span()=> self.myself_downgrade()});
+        Ok((quote_spanned! {span=> self.#accessor_ident().await}, wrap))
+    }
 
-        trait_constructor.add_method(downgrade_method);
+    // Methods to bind a `late_bind` agent/child to its app/parent and to wait for the binding.
+    fn setup_late_bind_methods<D: ProducerDescriptor>(&self, child_args: &ChildArgs<D>) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let span = child_args
+            .late_bind()
+            .as_ref()
+            .map_or(child_args.span(), |l| l.final_span());
+        let parent_type = child_args.parent_type();
+        let parent_base_ident = child_args.parent_base_ident();
+        let parent_field_ident = ctx.impl_ctx().parent_field_ident(child_args);
+        let rc_strong_type = ctx.impl_details().ref_count_strong(span);
+        let rc_weak_type = ctx.impl_details().ref_count_weak(span);
+        let vis = ctx.input().vis().to_token_stream();
 
-        ctx.impl_ctx_mut().add_trait(trait_constructor);
+        let mut bind_method = FXFnConstructor::new(format_ident!(
+            "bind_{}",
+            parent_base_ident,
+            span = parent_base_ident.span()
+        ));
+        bind_method
+            .set_self_borrow(true)
+            .set_span(span)
+            .set_vis(&vis)
+            .add_param(quote_spanned! {span=> #parent_base_ident: &#rc_strong_type<#parent_type>})
+            .set_ret_stmt(quote_spanned! {span=>
+                self.#parent_field_ident.bind(#rc_strong_type::downgrade(#parent_base_ident))
+            })
+            .add_attribute_toks(quote_spanned! {span=> #[allow(dead_code)]})?;
+
+        ctx.add_method(bind_method);
+
+        if ctx.arg_props().mode_async().is_some_and(|p| *p) {
+            let mut ready_method = FXFnConstructor::new(format_ident!(
+                "{}_ready",
+                parent_base_ident,
+                span = parent_base_ident.span()
+            ));
+            ready_method
+                .set_self_borrow(true)
+                .set_span(span)
+                .set_vis(&vis)
+                .set_async(FXProp::new(true, Some(span)))
+                .set_ret_type(quote_spanned! {span=> ::std::option::Option<#rc_strong_type<#parent_type>>})
+                .add_statement(quote_spanned! {span=> self.#parent_field_ident.wait().await;})
+                .set_ret_stmt(quote_spanned! {span=> #rc_weak_type::upgrade(&self.#parent_field_ident.get())})
+                .add_attribute_toks(quote_spanned! {span=> #[allow(dead_code)]})?;
+
+            ctx.add_method(ready_method);
+        }
 
         Ok(())
     }
 
-    fn setup_struct_as_parentish(&self) -> darling::Result<()> {
+    // With `as` the trait methods are complemented with inherent methods named after the parent base ident.
+    fn setup_renamed_methods<D: ProducerDescriptor>(&self, child_args: &ChildArgs<D>) -> darling::Result<()> {
         let ctx = self.ctx();
-        let args = &self.args;
-        let std_args = &self.args.std_args;
-        let arg_props = ctx.arg_props();
-
-        // Mark the parentish struct as reference counted unless it is already set.
-        if !*arg_props.rc() {
-            let is_set_rc = std_args.rc().is_set();
-            if !*is_set_rc {
-                let is_parent = args.parent.is_set();
-
-                if std_args.rc().is_some() {
-                    return Err(darling::Error::custom(format!(
-                        "`rc` cannot be disabled for a {} struct",
-                        if *is_parent { "parent" } else { "application" }
-                    ))
-                    .with_span(&is_set_rc.final_span()));
-                }
-
-                let myself_name = ctx.impl_ctx().myself_name()?.to_string();
-                let parentish_span = args.app.is_set().or(is_parent).final_span();
-                ctx.impl_ctx_mut()
-                    .add_fxstruct_arg(quote_spanned! {parentish_span=> rc(#myself_name)});
-            }
+        let span = child_args.span();
+        let parent_base_ident = child_args.parent_base_ident();
+        let trait_name = D::child_trait_name(span);
+        let rc_assoc = D::rc_assoc_type(span);
+        let weak_assoc = D::weak_assoc_type(span);
+        let vis = ctx.input().vis().to_token_stream();
+
+        let methods = [
+            (parent_base_ident.clone(), D::base_name().to_string(), rc_assoc),
+            (
+                format_ident!("{}_downgrade", parent_base_ident, span = parent_base_ident.span()),
+                format!("{}_downgrade", D::base_name()),
+                weak_assoc,
+            ),
+        ];
+
+        for (method_ident, trait_method, ret_assoc) in methods {
+            let trait_method = format_ident!("{}", trait_method, span = span);
+            let mut method = FXFnConstructor::new(method_ident);
+            method
+                .set_self_borrow(true)
+                .set_span(span)
+                .set_vis(&vis)
+                .set_ret_type(quote_spanned! {span=> <Self as #trait_name>::#ret_assoc})
+                .set_ret_stmt(quote_spanned! {span=> <Self as #trait_name>::#trait_method(self)})
+                .add_attribute_toks(quote_spanned! {span=> #[allow(dead_code)]})?;
+            ctx.add_method(method);
         }
 
         Ok(())
     }
 
-    fn setup_struct_as_childish(&self) -> darling::Result<()> {
+    // Non-panicking accessor to complement the one with unwrap policy applied. `unwrap` is not allowed together with
+    // `rc_strong`, hence the reference is always weak here.
+    fn setup_try_method<D: ProducerDescriptor>(&self, child_args: &ChildArgs<D>) -> darling::Result<()> {
         let ctx = self.ctx();
-        let args = &self.args;
-        let std_args = &self.args.std_args;
-        let arg_props = ctx.arg_props();
-        let is_childish = args.child.is_set().or(args.agent.is_set());
+        let span = child_args.span();
+        let parent_type = child_args.parent_type();
+        let parent_base_ident = child_args.parent_base_ident();
+        let parent_weak = self.parent_weak_ref(child_args, span);
+        let rc_strong_type = ctx.impl_details().ref_count_strong(span);
+        let rc_weak_type = ctx.impl_details().ref_count_weak(span);
+        let vis = ctx.input().vis().to_token_stream();
+
+        let mut try_method = FXFnConstructor::new(format_ident!(
+            "try_{}",
+            parent_base_ident,
+            span = parent_base_ident.span()
+        ));
+        try_method
+            .set_self_borrow(true)
+            .set_span(span)
+            .set_vis(&vis)
+            .set_ret_type(quote_spanned! {span=> ::std::option::Option<#rc_strong_type<#parent_type>>})
+            .set_ret_stmt(quote_spanned! {span=> #rc_weak_type::upgrade(#parent_weak)})
+            .add_attribute_toks(quote_spanned! {span=> #[allow(dead_code)]})?;
+
+        ctx.add_method(try_method);
+
+        Ok(())
+    }
+
+    // Methods that only hold a strong reference to the parent for the duration of a closure call.
+    fn setup_scoped_methods<D: ProducerDescriptor>(
+        &self,
+        child_args: &ChildArgs<D>,
+        final_unwrap: &TokenStream,
+        access_wrap: &AccessWrap,
+        async_access: Option<(TokenStream, AccessWrap)>,
+    ) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let span = child_args.span();
+        let parent_ref_type = child_args.parent_ref_type();
+        let parent_base_ident = child_args.parent_base_ident();
+        let parent_field_ident = ctx.impl_ctx().parent_field_ident(child_args);
+        let parent_weak = self.parent_weak_ref(child_args, span);
+        let is_rc_strong = child_args.rc_strong().is_set();
+        let rc_strong_type = ctx.impl_details().ref_count_strong(span);
+        let rc_weak_type = ctx.impl_details().ref_count_weak(span);
+        let vis = ctx.input().vis().to_token_stream();
+
+        let (upgrade, borrowed) = if *is_rc_strong {
+            (
+                quote_spanned! {span=> #rc_strong_type::clone(&self.#parent_field_ident)},
+                quote_spanned! {span=> &*self.#parent_field_ident},
+            )
+        }
+        else {
+            let upgrade = quote_spanned! {span=> #rc_weak_type::upgrade(#parent_weak) #final_unwrap};
+            (upgrade.clone(), quote_spanned! {span=> &*#upgrade})
+        };
+
+        let mut with_method = FXFnConstructor::new(format_ident!(
+            "with_{}",
+            parent_base_ident,
+            span = parent_base_ident.span()
+        ));
+        with_method
+            .set_span(span)
+            .set_vis(&vis)
+            .maybe_add_generic(Some(quote_spanned! {span=> FXPRET}))
+            .maybe_add_generic(Some(quote_spanned! {span=> FXPFN}))
+            .add_where_bound(quote_spanned! {span=> FXPFN: ::std::ops::FnOnce(&#parent_ref_type) -> FXPRET})
+            .add_param(quote_spanned! {span=> f: FXPFN})
+            .set_ret_type(access_wrap.wrap(quote_spanned! {span=> FXPRET}, span))
+            .set_ret_stmt(if let AccessWrap::Bare = access_wrap {
+                quote_spanned! {span=> f(#borrowed)}
+            }
+            else {
+                quote_spanned! {span=> #upgrade.map(|#parent_base_ident| f(&*#parent_base_ident))}
+            })
+            .add_attribute_toks(quote_spanned! {span=> #[allow(dead_code)]})?;
+
+        ctx.add_method(with_method);
+
+        if ctx.arg_props().mode_async().is_some_and(|p| *p) {
+            let (upgrade, access_wrap) = async_access.unwrap_or((upgrade, access_wrap.clone()));
+            // The closure only borrows the app/parent, the strong reference is dropped once its future completes.
+            let send = if *ctx.arg_props().syncish() {
+                quote_spanned! {span=> ::std::marker::Send +}
+            }
+            else {
+                quote! {}
+            };
+            let mut with_async_method = FXFnConstructor::new(format_ident!(
+                "with_{}_async",
+                parent_base_ident,
+                span = parent_base_ident.span()
+            ));
+            with_async_method
+                .set_span(span)
+                .set_vis(&vis)
+                .set_async(FXProp::new(true, Some(span)))
+                .maybe_add_generic(Some(quote_spanned! {span=> FXPRET}))
+                .maybe_add_generic(Some(quote_spanned! {span=> FXPFN}))
+                .add_where_bound(quote_spanned! {span=>
+                    FXPFN: for<'fxp> ::std::ops::FnOnce(
+                        &'fxp #parent_ref_type,
+                    ) -> ::std::pin::Pin<
+                        ::std::boxed::Box<dyn ::std::future::Future<Output = FXPRET> + #send 'fxp>,
+                    >
+                })
+                .add_param(quote_spanned! {span=> f: FXPFN})
+                .set_ret_type(access_wrap.wrap(quote_spanned! {span=> FXPRET}, span))
+                .set_ret_stmt(match access_wrap {
+                    AccessWrap::Bare => quote_spanned! {span=> f(&*#upgrade).await},
+                    AccessWrap::Option => quote_spanned! {span=>
+                        match #upgrade {
+                            ::std::option::Option::Some(#parent_base_ident) => {
+                                ::std::option::Option::Some(f(&*#parent_base_ident).await)
+                            }
+                            ::std::option::Option::None => ::std::option::Option::None,
+                        }
+                    },
+                    AccessWrap::Result(_) => quote_spanned! {span=>
+                        match #upgrade {
+                            ::std::result::Result::Ok(#parent_base_ident) => {
+                                ::std::result::Result::Ok(f(&*#parent_base_ident).await)
+                            }
+                            ::std::result::Result::Err(err) => ::std::result::Result::Err(err),
+                        }
+                    },
+                })
+                .add_attribute_toks(quote_spanned! {span=> #[allow(dead_code)]})?;
+
+            ctx.add_method(with_async_method);
+        }
+
+        Ok(())
+    }
+
+    fn impl_childish_trait<D: ProducerDescriptor>(&self, child_args: &ChildArgs<D>) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let child_args_span = child_args.span();
+        let trait_name = D::child_trait_name(child_args_span);
+        let mut trait_constructor = FXImplConstructor::new(trait_name);
+
+        trait_constructor
+            .set_span(child_args_span)
+            .set_from_generics(Some(ctx.input().generics().clone()))
+            .set_for_ident(ctx.input_ident());
+
+        self.setup_child_methods(&mut trait_constructor, child_args)?;
+        ctx.impl_ctx_mut().add_trait(trait_constructor);
+
+        self.impl_build_under(child_args)?;
+
+        if *child_args.rc_strong().is_set() {
+            self.impl_rc_strong_marker(child_args)?;
+        }
+
+        Ok(())
+    }
+
+    // Let `tree!` macro pick the builder of a node by its parent type.
+    fn impl_build_under<D: ProducerDescriptor>(&self, child_args: &ChildArgs<D>) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let args = &self.args;
+        let span = child_args.span();
+        let parent_type = child_args.parent_type();
+
+        if matches!(parent_type, syn::Type::TraitObject(_)) || child_args.is_late_bound() {
+            return Ok(());
+        }
+
+        // A type parameter is not known to be a parent.
+        if let syn::Type::Path(type_path) = parent_type {
+            if ctx
+                .input()
+                .generics()
+                .type_params()
+                .any(|param| type_path.path.is_ident(&param.ident))
+            {
+                return Ok(());
+            }
+        }
+
+        // When the app and the parent are of the same type the implementation for the parent binds both.
+        let parent_type_str = parent_type.to_token_stream().to_string();
+        let is_same_type = |ty: &syn::Type| ty.to_token_stream().to_string() == parent_type_str;
+        let also_app = if D::base_name() == "app" {
+            let child = args.child.as_ref().filter(|_| *args.child.is_set());
+            if child.is_some_and(|c| is_same_type(c.parent_type())) {
+                return Ok(());
+            }
+            false
+        }
+        else {
+            let agent = args.agent.as_ref().filter(|_| *args.agent.is_set());
+            if agent.is_some_and(|a| is_same_type(a.parent_type()) && a.is_late_bound()) {
+                // The app would not be bound, refuse to be a tree node altogether.
+                return Ok(());
+            }
+            agent.is_some_and(|a| is_same_type(a.parent_type()))
+        };
+
+        let trait_name = D::child_trait_name(span);
+        let handle_type = D::handle_type(span);
+        let bind_method = format_ident!("__fxplus_bind_{}", D::base_name(), span = span);
+        let build_under: syn::Path =
+            syn::parse2(quote_spanned! {span=> ::fieldx_plus::tree::__private::BuildUnder<#parent_type>})?;
+        let mut trait_constructor = FXImplConstructor::new(build_under);
+
+        let builder_method = format_ident!("__fxplus_{}_builder", D::base_name(), span = span);
+        let mut builder = quote_spanned! {span=> <Self as #trait_name>::#builder_method()};
+        if also_app {
+            builder = quote_spanned! {span=>
+                <Self as ::fieldx_plus::Agent>::__fxplus_bind_app(
+                    #builder,
+                    ::fieldx_plus::AppRef::new(parent.__fxplus_myself_downgrade()),
+                )
+            };
+        }
+
+        let mut builder_method = FXFnConstructor::new_associated(format_ident!("__fxplus_builder_under", span = span));
+        builder_method
+            .set_span(span)
+            .add_param(quote_spanned! {span=> parent: &#parent_type})
+            .set_ret_type(quote_spanned! {span=> <Self as #trait_name>::Builder})
+            .set_ret_stmt(quote_spanned! {span=>
+                <Self as #trait_name>::#bind_method(
+                    #builder,
+                    ::fieldx_plus::#handle_type::new(parent.__fxplus_myself_downgrade()),
+                )
+            });
+
+        trait_constructor
+            .set_span(span)
+            .set_from_generics(Some(ctx.input().generics().clone()))
+            .set_for_ident(ctx.input_ident())
+            .add_assoc_type(quote_spanned! {span=> type Builder = <Self as #trait_name>::Builder;})
+            .add_method(builder_method);
+
+        ctx.impl_ctx_mut().add_trait(trait_constructor);
+
+        Ok(())
+    }
+
+    // Let the parent struct detect fields that hold children with strong references back to it.
+    fn impl_rc_strong_marker<D: ProducerDescriptor>(&self, child_args: &ChildArgs<D>) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let span = child_args.span();
+        let parent_type = child_args.parent_type();
+        let trait_name: syn::Path =
+            syn::parse2(quote_spanned! {span=> ::fieldx_plus::leaks::__private::RcStrongTo<#parent_type>})?;
+        let mut trait_constructor = FXImplConstructor::new(trait_name);
+
+        trait_constructor
+            .set_span(span)
+            .set_from_generics(Some(ctx.input().generics().clone()))
+            .set_for_ident(ctx.input_ident());
+
+        ctx.impl_ctx_mut().add_trait(trait_constructor);
+
+        Ok(())
+    }
+
+    // An agent can be used to build its sibling agents. Applications have `AppSource` implemented via `Application`,
+    // parents get another implementation for themselves in `impl_parent_trait`.
+    fn impl_app_source<D: ProducerDescriptor>(&self, child_args: &ChildArgs<D>) -> darling::Result<()> {
+        let ctx = self.ctx();
+
+        if *self.args.app.is_set() {
+            return Ok(());
+        }
+
+        let span = child_args.span();
+        let parent_type = child_args.parent_type();
+        if *self.args.parent.is_set() {
+            // An agent of its own type is covered by the parent implementation.
+            let parent_type_str = parent_type.to_token_stream().to_string();
+            if parent_type_str == "Self" || *ctx.input_ident() == parent_type_str {
+                return Ok(());
+            }
+        }
+
+        let rc_weak = ctx.impl_details().ref_count_weak(span);
+        let app_weak_type = quote_spanned! {span=> #rc_weak<#parent_type>};
+        let trait_name: syn::Path = syn::parse2(quote_spanned! {span=> ::fieldx_plus::AppSource<#app_weak_type>})?;
+        let mut trait_constructor = FXImplConstructor::new(trait_name);
+
+        let mut app_weak_method = FXFnConstructor::new(format_ident!("app_weak", span = span));
+        app_weak_method
+            .set_span(span)
+            .set_ret_type(app_weak_type)
+            .set_ret_stmt(quote_spanned! {span=>
+                <Self as ::fieldx_plus::Agent>::app_downgrade(self).into_weak()
+            });
+
+        trait_constructor
+            .set_span(span)
+            .set_from_generics(Some(ctx.input().generics().clone()))
+            .set_for_ident(ctx.input_ident())
+            .add_method(app_weak_method);
+
+        ctx.impl_ctx_mut().add_trait(trait_constructor);
+
+        Ok(())
+    }
+
+    // Emit a deprecation warning for every field type which is declared as an `rc_strong` child of this struct.
+    fn setup_cycle_check(&self) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let mut field_types = vec![];
+
+        for field in ctx.input().fields() {
+            collect_nested_types(field.ty(), &mut field_types);
+        }
+
+        if field_types.is_empty() {
+            return Ok(());
+        }
+
+        let span = ctx.input_ident().span();
+        let mut check_method = FXFnConstructor::new_associated(format_ident!("__fxplus_cycle_check", span = span));
+        check_method
+            .set_span(span)
+            .add_statement(quote_spanned! {span=>
+                #[allow(unused_imports)]
+                use ::fieldx_plus::leaks::__private::NoCycleProbe;
+                #[allow(unused_imports)]
+                use ::fieldx_plus::leaks::__private::StrongCycleProbe;
+            })
+            .add_attribute_toks(quote_spanned! {span=> #[allow(dead_code)] #[doc(hidden)]})?;
+
+        for ty in field_types {
+            check_method.add_statement(quote_spanned! {ty.span()=>
+                (&::fieldx_plus::leaks::__private::CycleProbe::<#ty, Self>::new()).__fxplus_cycle_check();
+            });
+        }
+
+        ctx.add_method(check_method);
+
+        Ok(())
+    }
+
+    // Track application objects lifetime in debug builds.
+    fn setup_app_tracker(&self) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let span = self.args.track_leaks.span();
+        let struct_type = ctx.struct_type_toks();
+
+        let mut field_constructor = FXFieldConstructor::new(
+            format_ident!("__fxplus_app_tracker", span = span),
+            quote_spanned! {span=> ::fieldx_plus::leaks::AppTracker},
+            span,
+        );
+
+        let mut serde_off = ctx.impl_ctx().serde_off();
+        if !serde_off.is_empty() {
+            serde_off = quote_spanned! {span=> , #serde_off};
+        }
+        field_constructor.add_attribute_toks(quote_spanned! {span=>
+            #[fieldx(
+                lazy(off), predicate(off), clearer(off), get(off), set(off), builder(off),
+                default(::fieldx_plus::leaks::AppTracker::new::<#struct_type>()) #serde_off
+            )]
+        })?;
+
+        ctx.user_struct_mut().add_field(field_constructor);
+
+        Ok(())
+    }
+
+    fn impl_parent_trait(&self) -> darling::Result<()> {
+        let args = &self.args;
+        let ctx = self.ctx();
+
+        let trait_name: syn::Path = syn::parse2(quote! { ::fieldx_plus::Parent })?;
+        let weak_type = ctx.impl_details().ref_count_weak(args.parent.span());
+        let mut trait_constructor = FXImplConstructor::new(trait_name);
+
+        trait_constructor
+            .add_assoc_type(quote_spanned! {args.parent.span()=> type WeakSelf = #weak_type<Self>;})
+            .set_from_generics(Some(ctx.input().generics().clone()))
+            .set_for_ident(ctx.input_ident());
+
+        // Add fx_plus downgrade method
+        let mut downgrade_method =
+            FXFnConstructor::new(format_ident!("__fxplus_myself_downgrade", span = args.parent.span()));
+        downgrade_method
+            .set_span(args.parent.span())
+            .add_attribute_toks(quote_spanned! {args.parent.span()=> #[inline(always)]})?
+            .set_ret_type(quote_spanned! {args.parent.span()=> #weak_type<Self>})
+            .set_ret_stmt(quote_spanned! {args.parent.span()=> self.myself_downgrade()});
+
+        trait_constructor.add_method(downgrade_method);
+
+        ctx.impl_ctx_mut().add_trait(trait_constructor);
+
+        if *args.app.is_set() {
+            let span = args.app.span();
+            let trait_name: syn::Path = syn::parse2(quote_spanned! {span=> ::fieldx_plus::Application})?;
+            let mut trait_constructor = FXImplConstructor::new(trait_name);
+            trait_constructor
+                .set_span(span)
+                .set_from_generics(Some(ctx.input().generics().clone()))
+                .set_for_ident(ctx.input_ident());
+            ctx.impl_ctx_mut().add_trait(trait_constructor);
+        }
+        else {
+            // A parent can serve as the app of agents declared with `agent(ThisParent)`. A parent which is an agent
+            // itself also provides its own app to its sibling agents, see `impl_app_source`.
+            let span = args.parent.span();
+            let trait_name: syn::Path =
+                syn::parse2(quote_spanned! {span=> ::fieldx_plus::AppSource<#weak_type<Self>>})?;
+            let mut trait_constructor = FXImplConstructor::new(trait_name);
+
+            let mut app_weak_method = FXFnConstructor::new(format_ident!("app_weak", span = span));
+            app_weak_method
+                .set_span(span)
+                .set_ret_type(quote_spanned! {span=> #weak_type<Self>})
+                .set_ret_stmt(quote_spanned! {span=> self.myself_downgrade()});
+
+            trait_constructor
+                .set_span(span)
+                .set_from_generics(Some(ctx.input().generics().clone()))
+                .set_for_ident(ctx.input_ident())
+                .add_method(app_weak_method);
+            ctx.impl_ctx_mut().add_trait(trait_constructor);
+        }
+
+        Ok(())
+    }
+
+    // Identity-based comparison and hashing of reference counted structs.
+    fn impl_identity(&self) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let span = self.args.identity.span();
+        let weak_type = ctx.impl_details().ref_count_weak(span);
+        let myself_ptr = |obj: TokenStream| {
+            quote_spanned! {span=> #weak_type::as_ptr(&::fieldx_plus::Parent::__fxplus_myself_downgrade(#obj))}
+        };
+        let self_ptr = myself_ptr(quote_spanned! {span=> self});
+        let other_ptr = myself_ptr(quote_spanned! {span=> other});
+
+        let mut eq_method = FXFnConstructor::new(format_ident!("eq", span = span));
+        eq_method
+            .set_span(span)
+            .add_param(quote_spanned! {span=> other: &Self})
+            .set_ret_type(quote_spanned! {span=> bool})
+            .set_ret_stmt(quote_spanned! {span=> ::std::ptr::eq(#self_ptr, #other_ptr)});
+
+        let mut hash_method = FXFnConstructor::new(format_ident!("hash", span = span));
+        hash_method
+            .set_span(span)
+            .maybe_add_generic(Some(quote_spanned! {span=> FXPHASHER: ::std::hash::Hasher}))
+            .add_param(quote_spanned! {span=> state: &mut FXPHASHER})
+            .set_ret_stmt(quote_spanned! {span=> ::std::ptr::hash(#self_ptr, state)});
+
+        let mut partial_cmp_method = FXFnConstructor::new(format_ident!("partial_cmp", span = span));
+        partial_cmp_method
+            .set_span(span)
+            .add_param(quote_spanned! {span=> other: &Self})
+            .set_ret_type(quote_spanned! {span=> ::std::option::Option<::std::cmp::Ordering>})
+            .set_ret_stmt(quote_spanned! {span=> ::std::option::Option::Some(::std::cmp::Ord::cmp(self, other))});
+
+        let mut cmp_method = FXFnConstructor::new(format_ident!("cmp", span = span));
+        cmp_method
+            .set_span(span)
+            .add_param(quote_spanned! {span=> other: &Self})
+            .set_ret_type(quote_spanned! {span=> ::std::cmp::Ordering})
+            .set_ret_stmt(quote_spanned! {span=> #self_ptr.cmp(&#other_ptr)});
+
+        let identity_traits: [(TokenStream, Option<FXFnConstructor>); 5] = [
+            (quote_spanned! {span=> ::std::cmp::PartialEq}, Some(eq_method)),
+            (quote_spanned! {span=> ::std::cmp::Eq}, None),
+            (quote_spanned! {span=> ::std::hash::Hash}, Some(hash_method)),
+            (quote_spanned! {span=> ::std::cmp::PartialOrd}, Some(partial_cmp_method)),
+            (quote_spanned! {span=> ::std::cmp::Ord}, Some(cmp_method)),
+        ];
+
+        for (trait_name, method) in identity_traits {
+            let trait_name: syn::Path = syn::parse2(trait_name)?;
+            let mut trait_constructor = FXImplConstructor::new(trait_name);
+            trait_constructor
+                .set_span(span)
+                .set_from_generics(Some(ctx.input().generics().clone()))
+                .set_for_ident(ctx.input_ident());
+            if let Some(method) = method {
+                trait_constructor.add_method(method);
+            }
+            ctx.impl_ctx_mut().add_trait(trait_constructor);
+        }
+
+        Ok(())
+    }
+
+    fn setup_struct_as_parentish(&self) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let args = &self.args;
+        let std_args = &self.args.std_args;
+        let arg_props = ctx.arg_props();
+
+        // Mark the parentish struct as reference counted unless it is already set.
+        if !*arg_props.rc() {
+            let is_set_rc = std_args.rc().is_set();
+            if !*is_set_rc {
+                let is_parent = args.parent.is_set();
+
+                if std_args.rc().is_some() {
+                    return Err(darling::Error::custom(format!(
+                        "`rc` cannot be disabled for a {} struct",
+                        if *is_parent { "parent" } else { "application" }
+                    ))
+                    .with_span(&is_set_rc.final_span()));
+                }
+
+                let myself_name = ctx.impl_ctx().myself_name()?.to_string();
+                let parentish_span = args.app.is_set().or(is_parent).final_span();
+                ctx.impl_ctx_mut()
+                    .add_fxstruct_arg(quote_spanned! {parentish_span=> rc(#myself_name)});
+            }
+        }
+
+        Ok(())
+    }
+
+    fn setup_struct_as_childish(&self) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let args = &self.args;
+        let std_args = &self.args.std_args;
+        let arg_props = ctx.arg_props();
+        let is_childish = args.child.is_set().or(args.agent.is_set());
 
         let childish_span = is_childish.final_span();
         if *arg_props.needs_new() {
@@ -468,9 +1487,438 @@ impl FXPlusProducer {
                 .add_fxstruct_arg(quote_spanned! {childish_span=> new(off)});
         }
 
-        if !*arg_props.builder().is_set() {
-            ctx.impl_ctx_mut()
-                .add_fxstruct_arg(quote_spanned! {childish_span=> builder});
+        Ok(())
+    }
+
+    // `true` if the builder reports `BuildError`: requested explicitly with `build_error`, or implied by `check_alive`,
+    // unless the struct has its own builder error type or `post_build` method.
+    fn is_build_error(&self) -> bool {
+        let args = &self.args;
+        let arg_props = self.ctx().arg_props();
+        let agent = args.agent.as_ref().filter(|_| *args.agent.is_set());
+        let child = args.child.as_ref().filter(|_| *args.child.is_set());
+        let is_checked = agent.is_some_and(|a| a.is_checked_alive()) || child.is_some_and(|c| c.is_checked_alive());
+        (*args.build_error.is_set() || is_checked)
+            && !*arg_props.builder_has_error_type()
+            && !*arg_props.has_post_build()
+    }
+
+    // With `build_error`, make the builder report a missing app/parent with `BuildError::ParentNotSet`. Also check if
+    // the app/parent is still alive for relationships with `check_alive`, which implies `build_error`. A custom builder
+    // error type or `post_build` method are left intact since `BuildError` would be incompatible with them.
+    fn setup_builder_errors(
+        &self,
+        fxstruct_args: &mut Vec<TokenStream>,
+        post_build: &mut Vec<TokenStream>,
+    ) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let args = &self.args;
+        let arg_props = ctx.arg_props();
+        let mut parents = vec![];
+        let mut checks = vec![];
+
+        if let Some(agent_args) = args.agent.as_ref().filter(|_| *args.agent.is_set()) {
+            self.collect_builder_parent(agent_args, &mut parents, &mut checks);
+        }
+        if let Some(child_args) = args.child.as_ref().filter(|_| *args.child.is_set()) {
+            self.collect_builder_parent(child_args, &mut parents, &mut checks);
+        }
+
+        if !*args.build_error.is_set() && checks.is_empty() {
+            // The error type of fieldx is kept.
+            return Ok(());
+        }
+
+        let is_custom = arg_props.builder_has_error_type().or(arg_props.has_post_build());
+        if *is_custom {
+            if let Some((span, _)) = checks.first() {
+                return Err(darling::Error::custom(
+                    "`check_alive` cannot be used with custom builder `error` or `post_build`",
+                )
+                .with_span(span));
+            }
+            return Ok(());
+        }
+
+        // fieldx calls `post_build` of a reference-counted struct where it cannot fail.
+        let is_rc = arg_props.rc().or(args.app.is_set()).or(args.parent.is_set());
+        if *is_rc {
+            if let Some((span, _)) = checks.first() {
+                return Err(
+                    darling::Error::custom("`check_alive` cannot be used with a reference-counted struct")
+                        .with_span(span),
+                );
+            }
+        }
+
+        let span = ctx.input_ident().span();
+        let input_ident = ctx.input_ident();
+        let (_, ty_generics, _) = ctx.input().generics().split_for_impl();
+        let turbofish = ty_generics.as_turbofish();
+        let builder_args = vec![quote_spanned! {span=>
+            error(::fieldx_plus::error::BuildError, #input_ident #turbofish::__fxplus_builder_error)
+        }];
+
+        let mut error_method = FXFnConstructor::new_associated(format_ident!("__fxplus_builder_error", span = span));
+        error_method
+            .set_span(span)
+            .add_param(quote_spanned! {span=> field: ::std::string::String})
+            .set_ret_type(quote_spanned! {span=> ::fieldx_plus::error::BuildError})
+            .set_ret_stmt(quote_spanned! {span=>
+                ::fieldx_plus::error::BuildError::__field_not_set::<Self>(field, &[ #( #parents ),* ])
+            });
+        ctx.add_method(error_method);
+
+        post_build.extend(checks.into_iter().map(|(_, check)| check));
+
+        inject_builder_args(fxstruct_args, builder_args)
+    }
+
+    // Statements collected by `check_alive` and `inherit` are executed by the builder's `post_build` method. It only
+    // fails with the builder error type.
+    fn setup_post_build(
+        &self,
+        fxstruct_args: &mut Vec<TokenStream>,
+        post_build: Vec<TokenStream>,
+    ) -> darling::Result<()> {
+        if post_build.is_empty() {
+            return Ok(());
+        }
+
+        let ctx = self.ctx();
+        let span = ctx.input_ident().span();
+        let error_type = if self.is_build_error() {
+            Some(quote_spanned! {span=> ::fieldx_plus::error::BuildError})
+        }
+        else {
+            ctx.arg_props()
+                .builder_error_type()
+                .map(|error_type| error_type.to_token_stream())
+        };
+
+        let mut post_build_method = FXFnConstructor::new(format_ident!("__fxplus_post_build", span = span));
+        post_build_method
+            .set_span(span)
+            .set_self_borrow(false)
+            // Inherited fields are assigned to.
+            .set_self_mut(!self.inherited_fields().is_empty())
+            .add_attribute_toks(quote_spanned! {span=> #[allow(clippy::useless_conversion)]})?;
+        if let Some(error_type) = error_type {
+            post_build_method
+                .set_ret_type(quote_spanned! {span=> ::std::result::Result<Self, #error_type>})
+                .set_ret_stmt(quote_spanned! {span=> ::std::result::Result::Ok(self)});
+        }
+        else {
+            post_build_method
+                .set_ret_type(quote_spanned! {span=> Self})
+                .set_ret_stmt(quote_spanned! {span=> self});
+        }
+        for statement in post_build {
+            post_build_method.add_statement(statement);
+        }
+        ctx.add_method(post_build_method);
+
+        inject_builder_args(
+            fxstruct_args,
+            vec![quote_spanned! {span=> post_build(__fxplus_post_build)}],
+        )
+    }
+
+    fn collect_builder_parent<D: ProducerDescriptor>(
+        &self,
+        child_args: &ChildArgs<D>,
+        parents: &mut Vec<TokenStream>,
+        checks: &mut Vec<(Span, TokenStream)>,
+    ) {
+        if child_args.is_late_bound() {
+            // Not set with the builder.
+            return;
+        }
+
+        let ctx = self.ctx();
+        let span = child_args.span();
+        let field_name = ctx.impl_ctx().parent_field_ident(child_args).to_string();
+        let role = D::role_variant(span);
+        parents.push(quote_spanned! {span=> (#field_name, ::fieldx_plus::error::Role::#role)});
+
+        if child_args.is_checked_alive() {
+            let parent_field_ident = ctx.impl_ctx().parent_field_ident(child_args);
+            let parent_type = child_args.parent_type();
+            let rc_weak_type = ctx.impl_details().ref_count_weak(span);
+            let check_span = child_args.check_alive().as_ref().map_or(span, |c| c.final_span());
+            checks.push((
+                check_span,
+                quote_spanned! {span=>
+                    if #rc_weak_type::strong_count(&self.#parent_field_ident) == 0 {
+                        return ::std::result::Result::Err(::fieldx_plus::error::BuildError::ParentGoneAtBuild(
+                            ::fieldx_plus::ParentGone::for_role::<Self, #parent_type>(
+                                ::fieldx_plus::error::Role::#role,
+                            ),
+                        ));
+                    }
+                },
+            ));
+        }
+    }
+
+    fn typestate_builder_ident(&self) -> syn::Ident {
+        let input_ident = self.ctx().input_ident();
+        format_ident!("{}TypestateBuilder", input_ident, span = input_ident.span())
+    }
+
+    // Generic arguments of the typestate builder: those of the struct followed by the state.
+    fn typestate_generic_args(&self, state: TokenStream) -> TokenStream {
+        let args = self.ctx().input().generics().params.iter().map(|param| match param {
+            syn::GenericParam::Lifetime(lt) => lt.lifetime.to_token_stream(),
+            syn::GenericParam::Type(ty) => ty.ident.to_token_stream(),
+            syn::GenericParam::Const(c) => c.ident.to_token_stream(),
+        });
+        quote! { < #( #args, )* #state > }
+    }
+
+    // A wrapper around the fieldx builder which only gets the `build` method when the app/parent is set.
+    fn setup_typestate_builder<D: ProducerDescriptor>(&self, child_args: &ChildArgs<D>) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let args = &self.args;
+        let arg_props = ctx.arg_props();
+        let span = child_args
+            .typestate_builder()
+            .as_ref()
+            .map_or(child_args.span(), |t| t.final_span());
+
+        if *args.agent.is_set() && *args.child.is_set() {
+            return Err(darling::Error::custom(
+                "`typestate_builder` cannot be used with a struct which is both an agent and a child",
+            )
+            .with_span(&span));
+        }
+
+        let input_ident = ctx.input_ident();
+        let generics = ctx.input().generics();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let mut struct_generics = generics.clone();
+        struct_generics.params.push(syn::parse2(
+            quote_spanned! {span=> FXPState = ::fieldx_plus::typestate::Unbound},
+        )?);
+        let mut state_generics = generics.clone();
+        state_generics
+            .params
+            .push(syn::parse2(quote_spanned! {span=> FXPState})?);
+        let (state_impl_generics, ..) = state_generics.split_for_impl();
+        let typestate_ident = self.typestate_builder_ident();
+        let builder_ident = arg_props.builder_ident();
+        let vis = arg_props.builder_struct_visibility();
+        let state_args = self.typestate_generic_args(quote_spanned! {span=> FXPState});
+        let bound_args = self.typestate_generic_args(quote_spanned! {span=> ::fieldx_plus::typestate::Bound});
+        let unbound_args = self.typestate_generic_args(quote_spanned! {span=> ::fieldx_plus::typestate::Unbound});
+        let trait_name = D::child_trait_name(span);
+        let fxp_assoc = D::fxp_assoc_type(span);
+        let parent_base_ident = child_args.parent_base_ident();
+        let parent_setter_ident = self.builder_setter_ident(&parent_base_ident);
+
+        // Field setters are forwarded to the fieldx builder in any state.
+        let mut setters = vec![];
+        for field in ctx.input().fields() {
+            let fctx = FXFieldCtx::new(field.clone(), self.ctx.clone());
+            // The same condition fieldx uses to decide whether a field has a builder setter.
+            let has_setter = fctx.forced_builder().or(fctx.builder());
+            if *fctx.skipped() || !*has_setter {
+                continue;
+            }
+
+            let field_span = fctx.span();
+            let setter_ident = fctx.builder_ident();
+            let setter_vis = fctx.builder_method_visibility();
+            let ty = fctx.ty();
+            let (generic, value_type) = if *fctx.builder_into() {
+                (
+                    quote_spanned! {field_span=> <FXVALINTO: ::std::convert::Into<#ty>>},
+                    quote_spanned! {field_span=> FXVALINTO},
+                )
+            }
+            else {
+                (quote! {}, ty.to_token_stream())
+            };
+            setters.push(quote_spanned! {field_span=>
+                #[inline(always)]
+                #setter_vis fn #setter_ident #generic(self, value: #value_type) -> Self {
+                    #typestate_ident {
+                        inner:  self.inner.#setter_ident(value),
+                        _state: ::std::marker::PhantomData,
+                    }
+                }
+            });
+        }
+
+        let is_rc = arg_props.rc().or(args.app.is_set()).or(args.parent.is_set());
+        let ok_type = if *is_rc {
+            let rc_strong = ctx.impl_details().ref_count_strong(span);
+            quote_spanned! {span=> #rc_strong<#input_ident #ty_generics>}
+        }
+        else {
+            quote_spanned! {span=> #input_ident #ty_generics}
+        };
+        let err_type = if let Some(error_type) = arg_props.builder_error_type() {
+            error_type.to_token_stream()
+        }
+        else if self.is_build_error() {
+            quote_spanned! {span=> ::fieldx_plus::error::BuildError}
+        }
+        else {
+            quote_spanned! {span=> ::fieldx::error::FieldXError}
+        };
+
+        let struct_doc = format!(
+            "Typestate builder of [`{input_ident}`]. `build()` is only available once the {} is set with `{}()`.",
+            D::base_name(),
+            parent_setter_ident
+        );
+
+        ctx.impl_ctx_mut().add_item(quote_spanned! {span=>
+            #[doc = #struct_doc]
+            #vis struct #typestate_ident #struct_generics #where_clause {
+                inner:  #builder_ident #ty_generics,
+                _state: ::std::marker::PhantomData<FXPState>,
+            }
+
+            impl #state_impl_generics #typestate_ident #state_args #where_clause {
+                #( #setters )*
+            }
+
+            impl #impl_generics #typestate_ident #unbound_args #where_clause {
+                #[inline(always)]
+                #vis fn #parent_setter_ident(
+                    self,
+                    #parent_base_ident: <#input_ident #ty_generics as ::fieldx_plus::#trait_name>::#fxp_assoc,
+                ) -> #typestate_ident #bound_args {
+                    #typestate_ident {
+                        inner:  self.inner.#parent_setter_ident(#parent_base_ident),
+                        _state: ::std::marker::PhantomData,
+                    }
+                }
+            }
+
+            impl #impl_generics #typestate_ident #bound_args #where_clause {
+                #vis fn build(&mut self) -> ::std::result::Result<#ok_type, #err_type> {
+                    self.inner.build()
+                }
+            }
+        });
+
+        let mut typestate_method = FXFnConstructor::new_associated(format_ident!("typestate_builder", span = span));
+        typestate_method
+            .set_span(span)
+            .set_vis(vis.to_token_stream())
+            .set_ret_type(quote_spanned! {span=> #typestate_ident #unbound_args})
+            .set_ret_stmt(quote_spanned! {span=>
+                #typestate_ident {
+                    inner:  Self::builder(),
+                    _state: ::std::marker::PhantomData,
+                }
+            });
+        ctx.add_method(typestate_method);
+
+        Ok(())
+    }
+
+    // Let field-level `agent(...)`/`child(...)` of other structs find out what to build for a field of this type.
+    fn impl_lazy_target(&self) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let span = ctx.input_ident().span();
+        let trait_name: syn::Path = syn::parse2(quote_spanned! {span=> ::fieldx_plus::traits::__private::LazyTarget})?;
+        let mut trait_constructor = FXImplConstructor::new(trait_name);
+
+        trait_constructor
+            .set_span(span)
+            .set_from_generics(Some(ctx.input().generics().clone()))
+            .set_for_ident(ctx.input_ident())
+            .add_assoc_type(quote_spanned! {span=> type Target = Self;});
+
+        ctx.impl_ctx_mut().add_trait(trait_constructor);
+
+        Ok(())
+    }
+
+    // Lazy builder methods of fields declared with `agent(...)` or `child(...)`.
+    fn setup_lazy_builders(&self) -> darling::Result<()> {
+        let ctx = self.ctx();
+        let args = &self.args;
+        let input_ident = ctx.input_ident();
+
+        for (field_ident, field_args) in self.field_args.iter() {
+            let (build_args, macro_path, kind) = if let Some(agent) = field_args.agent() {
+                if !*args.app.is_set() && !*args.agent.is_set() {
+                    return Err(darling::Error::custom(
+                        "field-level `agent` requires the struct to be an `app` or an `agent`",
+                    )
+                    .with_span(&agent.final_span()));
+                }
+                (agent, quote! { ::fieldx_plus::agent_build! }, "agent")
+            }
+            else if let Some(child) = field_args.child() {
+                if !*args.parent.is_set() {
+                    return Err(
+                        darling::Error::custom("field-level `child` requires the struct to be a `parent`")
+                            .with_span(&child.final_span()),
+                    );
+                }
+                (child, quote! { ::fieldx_plus::child_build! }, "child")
+            }
+            else {
+                continue;
+            };
+
+            let Some(field) = ctx
+                .input()
+                .fields()
+                .into_iter()
+                .find(|f| f.ident().is_ok_and(|i| i == *field_ident))
+            else {
+                continue;
+            };
+            let fctx = FXFieldCtx::new(field.clone(), self.ctx.clone());
+            let span = build_args.final_span();
+
+            if !*fctx.lazy() {
+                return Err(
+                    darling::Error::custom(format!("field-level `{kind}` requires the field to be `lazy`"))
+                        .with_span(&span),
+                );
+            }
+
+            // The field type is either the agent/child itself or a reference-counted pointer to it. In the latter
+            // case the built object is converted unless the struct is reference-counted already.
+            let ty = fctx.ty();
+            let target = quote_spanned! {span=> <#ty as ::fieldx_plus::traits::__private::LazyTarget>::Target };
+            let inits = build_args
+                .inits()
+                .iter()
+                .map(|(name, expr)| quote_spanned! {span=> #name: #expr });
+            let build_expr = quote_spanned! {span=>
+                #macro_path(self, #target { #( #inits ),* }).map(::std::convert::Into::<#ty>::into)
+            };
+            let expect_msg = build_args.expect_arg().map_or_else(
+                || format!("Failed to build field `{field_ident}` of `{input_ident}`"),
+                |msg| msg.value(),
+            );
+
+            let ret_stmt = if !*fctx.fallible() {
+                quote_spanned! {span=> #build_expr.expect(#expect_msg) }
+            }
+            else if build_args.expect_arg().is_some() {
+                quote_spanned! {span=> ::std::result::Result::Ok(#build_expr.expect(#expect_msg)) }
+            }
+            else {
+                quote_spanned! {span=> #build_expr.map_err(::std::convert::Into::into) }
+            };
+
+            let mut lazy_builder = FXFnConstructor::new(fctx.lazy_ident().clone());
+            lazy_builder
+                .set_span(span)
+                .set_async(fctx.mode_async())
+                .set_ret_type(fctx.fallible_return_type(&fctx, ty)?)
+                .set_ret_stmt(ret_stmt);
+            ctx.add_method(lazy_builder);
         }
 
         Ok(())
@@ -497,26 +1945,101 @@ impl FXPlusProducer {
         if *is_parentish {
             self.impl_parent_trait()?;
             self.setup_struct_as_parentish()?;
+            self.setup_cycle_check()?;
+        }
+
+        let is_track_leaks = args.track_leaks.is_set();
+        if *is_track_leaks {
+            if !*is_app {
+                return Err(
+                    darling::Error::custom("`track_leaks` argument requires the struct to be an `app`")
+                        .with_span(&is_track_leaks.final_span()),
+                );
+            }
+            self.setup_app_tracker()?;
+        }
+
+        let is_identity = args.identity.is_set();
+        if *is_identity {
+            if !*is_parentish {
+                return Err(darling::Error::custom(
+                    "`identity` argument requires the struct to be an `app` or a `parent`",
+                )
+                .with_span(&is_identity.final_span()));
+            }
+            self.impl_identity()?;
+        }
+
+        let is_scoped = args.scoped.is_set();
+        if *is_scoped && !*is_childish {
+            return Err(
+                darling::Error::custom("`scoped` argument requires the struct to be an `agent` or a `child`")
+                    .with_span(&is_scoped.final_span()),
+            );
         }
 
         if *is_childish {
             self.setup_struct_as_childish()?;
         }
 
+        if let Some((_, getter)) = self.inherited_fields().first() {
+            if !*is_childish || (*is_agent && *is_child) {
+                return Err(darling::Error::custom(
+                    "`inherit` requires the struct to be either an `agent` or a `child`, but not both",
+                )
+                .with_span(*getter));
+            }
+        }
+
+        // Statements of the builder's `post_build` method.
+        let mut post_build = vec![];
+
         if *is_agent {
             let child_args = args.agent.as_ref().unwrap();
             self.impl_childish_trait(child_args)?;
             self.setup_parentish_field(child_args)?;
+            self.setup_inherited_fields(child_args, &mut post_build)?;
+            self.impl_app_source(child_args)?;
         }
 
         if *is_child {
             let child_args = args.child.as_ref().unwrap();
             self.impl_childish_trait(child_args)?;
             self.setup_parentish_field(child_args)?;
+            self.setup_inherited_fields(child_args, &mut post_build)?;
         }
 
         let mut fxstruct_args = std_args.to_arg_tokens();
         fxstruct_args.extend(ctx.impl_ctx().fxstruct_args().iter().map(|a| a.to_token_stream()));
+
+        if *is_childish {
+            self.setup_builder_errors(&mut fxstruct_args, &mut post_build)?;
+            self.setup_post_build(&mut fxstruct_args, post_build)?;
+            self.impl_lazy_target()?;
+        }
+        else if *args.build_error.is_set() {
+            return Err(darling::Error::custom(
+                "`build_error` argument requires the struct to be an `agent` or a `child`",
+            )
+            .with_span(&args.build_error.is_set().final_span()));
+        }
+
+        if *is_agent {
+            let child_args = args.agent.as_ref().unwrap();
+            if child_args.is_typestate_builder() {
+                self.setup_typestate_builder(child_args)?;
+            }
+        }
+
+        if *is_child {
+            let child_args = args.child.as_ref().unwrap();
+            if child_args.is_typestate_builder() {
+                self.setup_typestate_builder(child_args)?;
+            }
+        }
+
+        self.setup_lazy_builders()?;
+
         let mut struct_constructor = ctx.user_struct_mut();
         struct_constructor.add_attribute_toks(quote_spanned! {childish_span=>
             #[::fieldx::fxstruct( #( #fxstruct_args ),* )]
@@ -526,14 +2049,164 @@ impl FXPlusProducer {
         }
         struct_constructor.add_fields_from_receiver(ctx.input())?;
         let struct_toks = struct_constructor.to_token_stream();
+        let items = ctx.impl_ctx().items().clone();
 
         Ok(quote! {
             use ::fieldx_plus::traits::*;
             #struct_toks
+            #( #items )*
         })
     }
 }
 
+// Add sub-arguments to the `builder` argument of `fxstruct`, or add the argument if there is none.
+fn inject_builder_args(fxstruct_args: &mut Vec<TokenStream>, extra: Vec<TokenStream>) -> darling::Result<()> {
+    for arg in fxstruct_args.iter_mut() {
+        let Ok(meta) = syn::parse2::<syn::Meta>(arg.clone())
+        else {
+            continue;
+        };
+        if !meta.path().is_ident("builder") {
+            continue;
+        }
+        *arg = match meta {
+            syn::Meta::Path(path) => quote_spanned! {path.span()=> #path( #( #extra ),* )},
+            syn::Meta::List(list) if list.tokens.is_empty() => {
+                let path = &list.path;
+                quote_spanned! {path.span()=> #path( #( #extra ),* )}
+            }
+            syn::Meta::List(list) => {
+                let path = &list.path;
+                let tokens = &list.tokens;
+                quote_spanned! {path.span()=> #path( #tokens, #( #extra ),* )}
+            }
+            syn::Meta::NameValue(nv) => {
+                return Err(darling::Error::custom("Unexpected form of `builder` argument").with_span(&nv));
+            }
+        };
+        return Ok(());
+    }
+
+    fxstruct_args.push(quote! {builder( #( #extra ),* )});
+    Ok(())
+}
+
+// Name of the hidden field holding the value given to the builder for an inherited field.
+pub(crate) fn inherited_value_ident(field_ident: &syn::Ident) -> syn::Ident {
+    format_ident!("__fxplus_inherit_{}", field_ident, span = field_ident.span())
+}
+
+// An inherited field is taken out of the builder. Its setter is moved to a hidden optional field instead, which tells
+// the post-build step whether the caller has set the value. The field keeps its other `fieldx` arguments; the `builder`
+// argument and the docs go to the hidden field, named after the base name of the inherited one.
+pub(crate) fn split_inherited_field(field: &mut syn::Field) -> darling::Result<syn::Field> {
+    let Some(ref field_ident) = field.ident
+    else {
+        return Err(darling::Error::custom("`inherit` requires a named field").with_span(field));
+    };
+    let span = field_ident.span();
+
+    let mut metas = vec![];
+    let mut attrs = vec![];
+    for attr in field.attrs.drain(..) {
+        if attr.path().is_ident("fieldx") {
+            metas.extend(
+                attr.parse_args_with(syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)?,
+            );
+        }
+        else {
+            attrs.push(attr);
+        }
+    }
+
+    let (builder_metas, field_metas): (Vec<_>, Vec<_>) =
+        metas.into_iter().partition(|meta| meta.path().is_ident("builder"));
+    let base_name = field_metas
+        .iter()
+        .find(|meta| meta.path().is_ident("rename"))
+        .map_or_else(
+            || {
+                let name = field_ident.to_string();
+                quote_spanned! {span=> rename(#name)}
+            },
+            |meta| meta.to_token_stream(),
+        );
+    let docs = attrs.iter().filter(|attr| attr.path().is_ident("doc")).cloned();
+    let serde_off = if cfg!(feature = "serde") {
+        quote_spanned! {span=> , serde(off)}
+    }
+    else {
+        quote! {}
+    };
+
+    let value_ident = inherited_value_ident(field_ident);
+    let ty = &field.ty;
+    let value_field: syn::Field = syn::Field::parse_named.parse2(quote_spanned! {span=>
+        #( #docs )*
+        #[fieldx(
+            optional, #base_name, #( #builder_metas, )*
+            lazy(off), predicate(off), clearer(off), get(off), set(off) #serde_off
+        )]
+        #value_ident: #ty
+    })?;
+
+    attrs.push(syn::parse_quote_spanned! {span=> #[fieldx( #( #field_metas, )* builder(off) )]});
+    field.attrs = attrs;
+
+    Ok(value_field)
+}
+
+// Collect the type itself and all types it is parameterized with.
+fn collect_nested_types(ty: &syn::Type, types: &mut Vec<syn::Type>) {
+    match ty {
+        syn::Type::Path(type_path) => {
+            for segment in type_path.path.segments.iter() {
+                if let syn::PathArguments::AngleBracketed(ref args) = segment.arguments {
+                    for arg in args.args.iter() {
+                        if let syn::GenericArgument::Type(ref arg_ty) = arg {
+                            collect_nested_types(arg_ty, types);
+                        }
+                    }
+                }
+            }
+        }
+        syn::Type::Array(array) => collect_nested_types(&array.elem, types),
+        syn::Type::Group(group) => collect_nested_types(&group.elem, types),
+        syn::Type::Paren(paren) => collect_nested_types(&paren.elem, types),
+        syn::Type::Reference(reference) => collect_nested_types(&reference.elem, types),
+        syn::Type::Slice(slice) => collect_nested_types(&slice.elem, types),
+        syn::Type::Tuple(tuple) => {
+            for elem in tuple.elems.iter() {
+                collect_nested_types(elem, types);
+            }
+        }
+        // Trait objects, pointers, function types, etc. cannot hold a child in a way we can detect.
+        _ => return,
+    }
+
+    let ty_str = ty.to_token_stream().to_string();
+    if !types.iter().any(|t| t.to_token_stream().to_string() == ty_str) {
+        types.push(ty.clone());
+    }
+}
+
+// Human-readable form of a type for use in messages.
+fn type_to_string(ty: &syn::Type) -> String {
+    let mut out = ty.to_token_stream().to_string();
+    for (from, to) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        ("& ", "&"),
+    ] {
+        out = out.replace(from, to);
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -550,4 +2223,12 @@ mod tests {
         assert!(*args.std_args().rc().is_set());
         assert!(*args.std_args().lock().is_set());
     }
+
+    #[test]
+    fn test_type_to_string() {
+        let ty: syn::Type = syn::parse2(quote![crate::app::MyApp<Vec<u32>, &'static str>]).unwrap();
+        assert_eq!(type_to_string(&ty), "crate::app::MyApp<Vec<u32>, &'static str>");
+        let ty: syn::Type = syn::parse2(quote![dyn AppApi + Send]).unwrap();
+        assert_eq!(type_to_string(&ty), "dyn AppApi + Send");
+    }
 }
@@ -25,6 +25,9 @@ pub(crate) struct FXPlusMacroCtx {
     traits:        HashMap<syn::Path, FXImplConstructor>,
     #[fieldx(get)]
     fxstruct_args: Vec<TokenStream>,
+    // Items generated alongside the struct.
+    #[fieldx(get)]
+    items:         Vec<TokenStream>,
     myself_name:   OnceCell<syn::Ident>,
 }
 
@@ -53,6 +56,10 @@ impl FXPlusMacroCtx {
         }
     }
 
+    pub(crate) fn add_item(&mut self, item: TokenStream) {
+        self.items.push(item);
+    }
+
     pub(crate) fn add_trait(&mut self, constructor: FXImplConstructor) {
         self.traits.insert(constructor.ident().clone(), constructor);
     }
@@ -0,0 +1,224 @@
+// Crate-wide defaults for `fx_plus` arguments.
+//
+// `defaults!` produces a `fx_plus_defaults!` declarative macro which adds `#[__fx_plus_defaults(...)]` marker attribute
+// right after `#[fx_plus(...)]` of the item it wraps. `fx_plus` removes the marker and merges the defaults with its
+// explicit arguments before they're parsed. The `fx_plus` attribute itself is kept intact for its call site to remain
+// in the user's hygiene context.
+//
+// Wrapping is the only way: an attribute can't be aliased with `macro_rules!` on stable, and `fx_plus` has no access to
+// the crate-wide state. Unwrapped items get no defaults.
+
+use darling::ast::NestedMeta;
+use proc_macro2::Group;
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use proc_macro2::TokenTree;
+use quote::quote_spanned;
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::parse::Parse;
+use syn::spanned::Spanned;
+use syn::Token;
+
+const DEFAULTS_ATTR: &str = "__fx_plus_defaults";
+
+// Arguments only one of which can be used at a time. If any of them is explicitly specified then none of the defaults
+// from the same group is used.
+const TOP_GROUPS: &[&[&str]] = &[&["sync", "async", "r#async", "mode"]];
+const CHILD_GROUPS: &[&[&str]] = &[
+    &["rc_strong", "unwrap"],
+    &["rc_strong", "late_bind"],
+    &["rc_strong", "check_alive"],
+    &["late_bind", "check_alive"],
+    &["late_bind", "typestate_builder"],
+];
+
+// Arguments of `agent(...)` and `child(...)`: the type with an optional `as` alias, and the rest.
+struct RawChildArgs {
+    head:  TokenStream,
+    metas: Vec<NestedMeta>,
+}
+
+impl Parse for RawChildArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut head = syn::Type::parse(input)?.into_token_stream();
+        if input.peek(Token![as]) {
+            let as_token = input.parse::<Token![as]>()?;
+            let ident = syn::Ident::parse_any(input)?;
+            head.extend(quote_spanned! {ident.span()=> #as_token #ident});
+        }
+
+        let mut metas = vec![];
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            metas = input
+                .parse_terminated(NestedMeta::parse, Token![,])?
+                .into_iter()
+                .collect();
+        }
+
+        Ok(Self { head, metas })
+    }
+}
+
+// Tokens produced by `fx_plus_defaults!` carry its hygiene context which would make `self` in the generated code
+// unresolvable. Give them the span of user's own tokens.
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(group.delimiter(), respan(group.stream(), span));
+                new_group.set_span(span);
+                TokenTree::Group(new_group)
+            }
+            mut tt => {
+                tt.set_span(span);
+                tt
+            }
+        })
+        .collect()
+}
+
+fn meta_name(meta: &NestedMeta) -> Option<String> {
+    match meta {
+        NestedMeta::Meta(meta) => Some(meta.path().to_token_stream().to_string()),
+        NestedMeta::Lit(_) => None,
+    }
+}
+
+fn meta_tokens(meta: &NestedMeta) -> Option<TokenStream> {
+    match meta {
+        NestedMeta::Meta(syn::Meta::List(list)) => Some(list.tokens.clone()),
+        _ => None,
+    }
+}
+
+// `true` if the default argument `name` is overridden by any of the explicitly set `names`.
+fn is_overridden(name: &str, names: &[String], groups: &[&[&str]]) -> bool {
+    names.iter().any(|n| n == name)
+        || groups
+            .iter()
+            .filter(|group| group.contains(&name))
+            .any(|group| names.iter().any(|n| group.contains(&n.as_str())))
+}
+
+fn merge_metas(explicit: &mut Vec<NestedMeta>, defaults: Vec<NestedMeta>, groups: &[&[&str]]) {
+    let names = explicit.iter().filter_map(meta_name).collect::<Vec<_>>();
+    explicit.extend(
+        defaults
+            .into_iter()
+            .filter(|d| meta_name(d).is_some_and(|name| !is_overridden(&name, &names, groups))),
+    );
+}
+
+fn merge_child_args(explicit: &NestedMeta, defaults: &NestedMeta) -> darling::Result<NestedMeta> {
+    let (Some(explicit_toks), Some(default_toks)) = (meta_tokens(explicit), meta_tokens(defaults))
+    else {
+        return Ok(explicit.clone());
+    };
+
+    let span = explicit.span();
+    let RawChildArgs { head, mut metas } = syn::parse2(explicit_toks)?;
+    let default_metas = NestedMeta::parse_meta_list(respan(default_toks, span))?;
+    merge_metas(&mut metas, default_metas, CHILD_GROUPS);
+
+    let NestedMeta::Meta(syn::Meta::List(list)) = explicit
+    else {
+        unreachable!("meta_tokens() only returns tokens of a list")
+    };
+    let path = &list.path;
+    Ok(syn::parse2(quote_spanned! {span=> #path(#head, #( #metas ),*) })?)
+}
+
+// Remove defaults marker attributes from the item and return their arguments.
+pub(crate) fn take_defaults(input: &mut syn::DeriveInput) -> Vec<TokenStream> {
+    let (defaults, attrs): (Vec<_>, Vec<_>) = input
+        .attrs
+        .drain(..)
+        .partition(|attr| attr.path().is_ident(DEFAULTS_ATTR));
+    input.attrs = attrs;
+    defaults
+        .into_iter()
+        .filter_map(|attr| match attr.meta {
+            syn::Meta::List(list) => Some(list.tokens),
+            _ => None,
+        })
+        .collect()
+}
+
+// Merge crate-wide defaults, if there are any, into the explicitly specified arguments.
+pub(crate) fn merge_defaults(
+    mut explicit: Vec<NestedMeta>,
+    defaults: Vec<TokenStream>,
+) -> darling::Result<Vec<NestedMeta>> {
+    let span = explicit.first().map_or_else(Span::call_site, |meta| meta.span());
+    let mut default_metas = vec![];
+    for toks in defaults {
+        default_metas.extend(NestedMeta::parse_meta_list(respan(toks, span))?);
+    }
+
+    let mut top_defaults = vec![];
+    for default_meta in default_metas {
+        let name = meta_name(&default_meta);
+        if matches!(name.as_deref(), Some("agent" | "child")) {
+            // Defaults for agents and children only apply to structs that are agents or children.
+            for meta in explicit.iter_mut() {
+                if meta_name(meta) == name {
+                    *meta = merge_child_args(meta, &default_meta)?;
+                }
+            }
+        }
+        else {
+            top_defaults.push(default_meta);
+        }
+    }
+
+    merge_metas(&mut explicit, top_defaults, TOP_GROUPS);
+
+    Ok(explicit)
+}
+
+pub(crate) fn produce_defaults_macro(input: TokenStream) -> darling::Result<TokenStream> {
+    // Make sure the defaults are syntactically valid before they're spread over the crate.
+    let _ = NestedMeta::parse_meta_list(input.clone())?;
+
+    Ok(quote_spanned! {Span::call_site()=>
+        #[allow(unused_macros)]
+        macro_rules! fx_plus_defaults {
+            (@munch [$($pre:tt)*] $pound:tt $attr:tt $($rest:tt)*) => {
+                $crate::fx_plus_defaults! { @check $attr [$($pre)*] $pound $attr $($rest)* }
+            };
+            (@check [fx_plus $($args:tt)*] [$($pre:tt)*] $pound:tt $attr:tt $($rest:tt)*) => {
+                $($pre)*
+                $pound $attr
+                #[__fx_plus_defaults(#input)]
+                $($rest)*
+            };
+            (@check [$($path:ident)? :: $(fieldx_plus ::)? fx_plus $($args:tt)*] $($rest:tt)*) => {
+                ::std::compile_error!(
+                    "`fx_plus_defaults!` only recognizes a bare `#[fx_plus(...)]`, use `use fieldx_plus::fx_plus;`"
+                );
+            };
+            (@check $other:tt [$($pre:tt)*] $pound:tt $attr:tt $($rest:tt)*) => {
+                $crate::fx_plus_defaults! { @attr [$($pre)*] $pound $attr $($rest)* }
+            };
+            (@attr [$($pre:tt)*] # $attr:tt $($rest:tt)*) => {
+                $crate::fx_plus_defaults! { @munch [$($pre)* # $attr] $($rest)* }
+            };
+            (@attr [$($pre:tt)*] $($rest:tt)*) => {
+                ::std::compile_error!("`fx_plus_defaults!` expects an item with `#[fx_plus(...)]` attribute");
+            };
+            ($($item:tt)*) => {
+                $crate::fx_plus_defaults! { @munch [] $($item)* }
+            };
+        }
+
+        #[allow(unused_imports)]
+        pub(crate) use fx_plus_defaults;
+
+        // `fx_plus_defaults!` refers to itself via `$crate::`, this fails early unless it is defined at the crate root.
+        #[allow(unused_imports)]
+        use crate::fx_plus_defaults as _;
+    })
+}
@@ -2,48 +2,127 @@
 
 mod codegen;
 mod ctx;
+mod defaults;
 mod traits;
 pub(crate) mod types;
 
 use codegen::FXPlusArgs;
 use codegen::FXPlusProducer;
 use darling::ast;
+use darling::ast::NestedMeta;
 use darling::FromDeriveInput;
 use darling::FromMeta;
 use fieldx_core::struct_receiver::FXStructReceiver;
 use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::spanned::Spanned;
 use syn::DeriveInput;
+use types::FXPlusFieldArgs;
 
-fn into_attr_args<ARG_TYPE>(args: proc_macro::TokenStream) -> darling::Result<ARG_TYPE>
+fn into_attr_args<ARG_TYPE>(args: proc_macro::TokenStream, defaults: Vec<TokenStream>) -> darling::Result<ARG_TYPE>
 where
     ARG_TYPE: FromMeta,
 {
     let arg_tokens: TokenStream = args.into();
-    let attr_args = ast::NestedMeta::parse_meta_list(arg_tokens.clone())?;
+    let mut attr_args = defaults::merge_defaults(ast::NestedMeta::parse_meta_list(arg_tokens.clone())?, defaults)?;
+    enable_builder(&mut attr_args)?;
     ARG_TYPE::from_list(&attr_args).map_err(|e| e.with_span(&arg_tokens))
 }
 
-fn into_struct_receiver<RECV>(input: &proc_macro::TokenStream) -> darling::Result<RECV>
+// Agents and children always have a builder. It is enabled before the arguments are parsed for the struct arguments
+// seen by fieldx and by our code generator to be the same.
+fn enable_builder(attr_args: &mut Vec<NestedMeta>) -> darling::Result<()> {
+    let find = |name: &str| {
+        attr_args.iter().find_map(|arg| match arg {
+            NestedMeta::Meta(meta) if meta.path().is_ident(name) => Some(meta.path().span()),
+            _ => None,
+        })
+    };
+    if find("builder").is_none() {
+        if let Some(span) = find("agent").or_else(|| find("child")) {
+            attr_args.push(syn::parse2(quote_spanned! {span=> builder})?);
+        }
+    }
+    Ok(())
+}
+
+fn into_struct_receiver<RECV>(di: &DeriveInput) -> darling::Result<RECV>
 where
     RECV: FromDeriveInput,
 {
-    let di: DeriveInput = syn::parse::<DeriveInput>(input.clone())?;
-    RECV::from_derive_input(&di)
+    RECV::from_derive_input(di)
+}
+
+// Field-level `#[fx_plus(...)]` attributes are ours and must not be passed over to fieldx.
+fn take_field_args(di: &mut DeriveInput) -> darling::Result<Vec<(syn::Ident, FXPlusFieldArgs)>> {
+    let mut field_args = vec![];
+    let syn::Data::Struct(ref mut data) = di.data
+    else {
+        return Ok(field_args);
+    };
+
+    let mut inherited_values = vec![];
+    for field in data.fields.iter_mut() {
+        let (plus_attrs, attrs): (Vec<_>, Vec<_>) =
+            field.attrs.drain(..).partition(|attr| attr.path().is_ident("fx_plus"));
+        field.attrs = attrs;
+        if plus_attrs.is_empty() {
+            continue;
+        }
+
+        let Some(ref ident) = field.ident
+        else {
+            return Err(darling::Error::custom("`fx_plus` field arguments require a named field").with_span(field));
+        };
+
+        let mut metas = vec![];
+        for attr in plus_attrs {
+            metas.extend(NestedMeta::parse_meta_list(attr.meta.require_list()?.tokens.clone())?);
+        }
+        let args = FXPlusFieldArgs::from_list(&metas)?;
+        let is_inherited = args.inherit().is_some();
+        field_args.push((ident.clone(), args));
+        if is_inherited {
+            inherited_values.push(codegen::split_inherited_field(field)?);
+        }
+    }
+
+    if let syn::Fields::Named(ref mut fields) = data.fields {
+        fields.named.extend(inherited_values);
+    }
+
+    Ok(field_args)
 }
 
 #[proc_macro_attribute]
 pub fn fx_plus(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let macro_args: FXPlusArgs = match into_attr_args(args) {
+    let mut di = match syn::parse::<DeriveInput>(input) {
+        Ok(di) => di,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let defaults = defaults::take_defaults(&mut di);
+    let macro_args: FXPlusArgs = match into_attr_args(args, defaults) {
         Ok(a) => a,
         Err(e) => return e.write_errors().into(),
     };
-    let struct_recv: FXStructReceiver = match into_struct_receiver(&input) {
+    let field_args = match take_field_args(&mut di) {
+        Ok(fa) => fa,
+        Err(e) => return e.write_errors().into(),
+    };
+    let struct_recv: FXStructReceiver = match into_struct_receiver(&di) {
         Ok(sr) => sr,
         Err(e) => return e.write_errors().into(),
     };
-    let tt = FXPlusProducer::new(macro_args, struct_recv)
+    let tt = FXPlusProducer::new(macro_args, field_args, struct_recv)
         .produce()
         .unwrap_or_else(|err| err.write_errors());
 
     tt.into()
 }
+
+#[proc_macro]
+pub fn defaults(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    defaults::produce_defaults_macro(input.into())
+        .unwrap_or_else(|err| err.write_errors())
+        .into()
+}
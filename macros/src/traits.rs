@@ -7,4 +7,6 @@ pub(crate) trait ProducerDescriptor: Clone {
     fn rc_assoc_type(span: Span) -> syn::Ident;
     fn weak_assoc_type(span: Span) -> syn::Ident;
     fn fxp_assoc_type(span: Span) -> syn::Ident;
+    fn handle_type(span: Span) -> syn::Ident;
+    fn role_variant(span: Span) -> syn::Ident;
 }
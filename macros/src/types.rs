@@ -12,10 +12,14 @@ use fieldx_aux::FXProp;
 use fieldx_aux::FXSetState;
 use fieldx_aux::FXString;
 use fieldx_aux::FXSynTuple;
+use fieldx_aux::FXSynValue;
 use fieldx_aux::FromNestAttr;
 use proc_macro2::Span;
+use proc_macro2::TokenStream;
 use proc_macro2::TokenTree;
 use quote::format_ident;
+use quote::quote_spanned;
+use quote::ToTokens;
 use std::marker::PhantomData;
 use std::ops::Deref;
 use syn::ext::IdentExt;
@@ -48,19 +52,35 @@ use syn::Token;
 #[fxstruct(default(off), get)]
 #[darling(and_then = Self::validate)]
 pub(crate) struct UnwrapArg {
-    off:         Flag,
+    off:               Flag,
     #[darling(rename = "expect")]
-    expect_arg:  Option<FXString>,
+    expect_arg:        Option<FXString>,
     #[darling(rename = "or")]
-    or_arg:      Option<FXSynTuple<(syn::Path, syn::Expr)>>,
+    or_arg:            Option<FXSynTuple<(syn::Path, syn::Expr)>>,
     #[darling(rename = "or_else")]
-    or_else_arg: Option<FXSynTuple<(syn::Path, syn::Expr)>>,
+    or_else_arg:       Option<FXSynTuple<(syn::Path, syn::Expr)>>,
+    #[darling(rename = "or_else_async")]
+    or_else_async_arg: Option<FXSynTuple<(syn::Path, syn::Expr)>>,
+    #[darling(rename = "error")]
+    error_arg:         Option<FXBool>,
+    #[darling(rename = "anyhow")]
+    anyhow_arg:        Option<FXBool>,
+    #[darling(rename = "eyre")]
+    eyre_arg:          Option<FXBool>,
+    #[darling(rename = "into")]
+    convert_arg:       Option<FXSynValue<syn::Type>>,
+    #[darling(rename = "default")]
+    default_arg:       Option<FXSynValue<syn::Expr>>,
+    also_try:          Option<FXBool>,
+    trace:             Option<FXSynValue<syn::Ident>>,
 }
 
 impl UnwrapArg {
     validate_exclusives! {
         "parent/app drop handling":
             expect_arg as "expect"; or_arg as "or"; or_else_arg as "or_else";
+            or_else_async_arg as "or_else_async"; error_arg as "error";
+            anyhow_arg as "anyhow"; eyre_arg as "eyre"; convert_arg as "into"; default_arg as "default";
     }
 
     fn validate(self) -> Result<Self, darling::Error> {
@@ -101,6 +121,12 @@ pub struct ChildArgsInner<D> {
     rc_strong:         FXBool,
     #[fieldx(optional, get(as_ref))]
     unwrap_parent:     FXNestingAttr<UnwrapArg>,
+    #[fieldx(optional, get(as_ref))]
+    late_bind:         FXBool,
+    #[fieldx(optional, get(as_ref))]
+    check_alive:       FXBool,
+    #[fieldx(optional, get(as_ref))]
+    typestate_builder: FXBool,
     #[fieldx(builder(off))]
     _d:                PhantomData<D>,
 }
@@ -108,13 +134,22 @@ pub struct ChildArgsInner<D> {
 #[derive(FromMeta, Debug)]
 #[darling(and_then = Self::validate)]
 struct _ChldArgs {
-    rc_strong:     Option<FXBool>,
+    rc_strong:         Option<FXBool>,
     #[darling(rename = "unwrap")]
-    unwrap_parent: Option<FXNestingAttr<UnwrapArg>>,
+    unwrap_parent:     Option<FXNestingAttr<UnwrapArg>>,
+    late_bind:         Option<FXBool>,
+    check_alive:       Option<FXBool>,
+    typestate_builder: Option<FXBool>,
 }
 
 impl _ChldArgs {
-    validate_exclusives! {"strong/weak parent": rc_strong; unwrap_parent;}
+    validate_exclusives! {
+        "strong/weak parent": rc_strong; unwrap_parent;
+        "strong/late parent": rc_strong; late_bind;
+        "strong/checked parent": rc_strong; check_alive;
+        "late/checked parent": late_bind; check_alive;
+        "late parent/typestate builder": late_bind; typestate_builder;
+    }
 
     fn validate(self) -> darling::Result<Self> {
         self.validate_exclusives()?;
@@ -139,6 +174,30 @@ impl<D: ProducerDescriptor> ChildArgs<D> {
             .cloned()
             .unwrap_or_else(|| format_ident!("{}", D::base_name(), span = self.span))
     }
+
+    pub fn is_late_bound(&self) -> bool {
+        self.late_bind().as_ref().is_some_and(|l| *l.is_set())
+    }
+
+    pub fn is_checked_alive(&self) -> bool {
+        self.check_alive().as_ref().is_some_and(|c| *c.is_set())
+    }
+
+    pub fn is_typestate_builder(&self) -> bool {
+        self.typestate_builder().as_ref().is_some_and(|t| *t.is_set())
+    }
+
+    // Parent type as it should appear behind a reference. A trait object with additional bounds, like
+    // `dyn AppApi + Send`, would be ambiguous in `&dyn AppApi + Send` form.
+    pub fn parent_ref_type(&self) -> TokenStream {
+        let parent_type = self.parent_type();
+        match parent_type {
+            syn::Type::TraitObject(ref trait_obj) if trait_obj.bounds.len() > 1 => {
+                quote_spanned! {parent_type.span()=> (#parent_type)}
+            }
+            _ => parent_type.to_token_stream(),
+        }
+    }
 }
 
 impl<D: ProducerDescriptor + std::fmt::Debug> Parse for ChildArgs<D> {
@@ -198,6 +257,15 @@ impl<D: ProducerDescriptor + std::fmt::Debug> Parse for ChildArgs<D> {
         if let Some(unwrap_parent) = ca.unwrap_parent {
             inner_builder = inner_builder.unwrap_parent(unwrap_parent);
         }
+        if let Some(late_bind) = ca.late_bind {
+            inner_builder = inner_builder.late_bind(late_bind);
+        }
+        if let Some(check_alive) = ca.check_alive {
+            inner_builder = inner_builder.check_alive(check_alive);
+        }
+        if let Some(typestate_builder) = ca.typestate_builder {
+            inner_builder = inner_builder.typestate_builder(typestate_builder);
+        }
 
         let inner = inner_builder
             .build()
@@ -215,6 +283,102 @@ impl<D: ProducerDescriptor> Deref for ChildArgs<D> {
     }
 }
 
+// Arguments of field-level `agent(...)` and `child(...)`: builder field initializers and the panic message to use if
+// the build fails.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LazyBuildArgs {
+    inits:      Vec<(syn::Ident, syn::Expr)>,
+    expect_arg: Option<syn::LitStr>,
+}
+
+impl LazyBuildArgs {
+    pub(crate) fn inits(&self) -> &[(syn::Ident, syn::Expr)] {
+        &self.inits
+    }
+
+    pub(crate) fn expect_arg(&self) -> Option<&syn::LitStr> {
+        self.expect_arg.as_ref()
+    }
+}
+
+impl FromMeta for LazyBuildArgs {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut args = Self::default();
+        let mut errors = darling::Error::accumulator();
+
+        for item in items {
+            match item {
+                NestedMeta::Meta(syn::Meta::NameValue(nv)) if nv.path.get_ident().is_some() => {
+                    args.inits
+                        .push((nv.path.get_ident().unwrap().clone(), nv.value.clone()));
+                }
+                NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("expect") => {
+                    if args.expect_arg.is_some() {
+                        errors.push(darling::Error::duplicate_field("expect").with_span(list));
+                    }
+                    else if let Some(message) = errors.handle(list.parse_args::<syn::LitStr>().map_err(Into::into)) {
+                        args.expect_arg = Some(message);
+                    }
+                }
+                _ => {
+                    errors.push(
+                        darling::Error::custom("expected `field = value` or `expect(\"message\")`").with_span(item),
+                    );
+                }
+            }
+        }
+
+        errors.finish_with(args)
+    }
+}
+
+impl FXSetState for LazyBuildArgs {
+    fn is_set(&self) -> FXProp<bool> {
+        FXProp::new(true, None)
+    }
+}
+
+impl FromNestAttr for LazyBuildArgs {
+    fn set_literals(self, literals: &[syn::Lit]) -> darling::Result<Self> {
+        self.no_literals(literals)?;
+        Ok(self)
+    }
+
+    fn for_keyword(_path: &syn::Path) -> darling::Result<Self> {
+        Ok(Default::default())
+    }
+}
+
+// Field-level `#[fx_plus(...)]` arguments.
+#[derive(FromMeta, Debug, Clone, Default)]
+#[fxstruct(default(off), get)]
+#[darling(and_then = Self::validate)]
+pub(crate) struct FXPlusFieldArgs {
+    agent:   Option<FXNestingAttr<LazyBuildArgs>>,
+    child:   Option<FXNestingAttr<LazyBuildArgs>>,
+    // Name of the app/parent getter to take the field value from.
+    inherit: Option<syn::Ident>,
+}
+
+impl FXPlusFieldArgs {
+    validate_exclusives! {
+        "lazy builder kind": agent; child;
+    }
+
+    fn validate(self) -> darling::Result<Self> {
+        self.validate_exclusives()?;
+        if let Some(ref inherit) = self.inherit {
+            if self.agent.is_some() || self.child.is_some() {
+                return Err(darling::Error::custom(
+                    "`inherit` cannot be used together with `agent` or `child` on the same field",
+                )
+                .with_span(inherit));
+            }
+        }
+        Ok(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use fieldx_aux::FXSetState;
@@ -235,4 +399,47 @@ mod tests {
         assert_eq!(cargs.parent_base_ident().to_string(), "up_node");
         assert!(*cargs.unwrap_parent().is_set());
     }
+
+    #[test]
+    fn test_unwrap_exclusives() {
+        let cargs: ChildArgs<AppDescriptor> = syn::parse2(quote! {App, unwrap(error)}).unwrap();
+        assert!(cargs.unwrap_parent().as_ref().unwrap().error_arg().is_some());
+
+        let Err(err) = syn::parse2::<ChildArgs<AppDescriptor>>(quote! {App, unwrap(error, expect("gone"))})
+        else {
+            panic!("conflicting unwrap arguments must be rejected");
+        };
+        assert!(err.to_string().contains("Conflicting arguments"));
+
+        let cargs: ChildArgs<AppDescriptor> = syn::parse2(quote! {App, unwrap(into(MyError))}).unwrap();
+        assert!(cargs.unwrap_parent().as_ref().unwrap().convert_arg().is_some());
+        assert!(syn::parse2::<ChildArgs<AppDescriptor>>(quote! {App, unwrap(into(MyError), error)}).is_err());
+
+        let cargs: ChildArgs<AppDescriptor> = syn::parse2(quote! {App, unwrap(expect("gone"), also_try)}).unwrap();
+        assert!(cargs.unwrap_parent().as_ref().unwrap().also_try().is_set_bool());
+
+        let cargs: ChildArgs<AppDescriptor> = syn::parse2(quote! {App, unwrap(or(E, E::Gone), trace(warn))}).unwrap();
+        assert_eq!(
+            cargs
+                .unwrap_parent()
+                .as_ref()
+                .unwrap()
+                .trace()
+                .as_ref()
+                .unwrap()
+                .value()
+                .to_string(),
+            "warn"
+        );
+    }
+
+    #[test]
+    fn test_dyn_parent_args() {
+        let input = quote! {dyn AppApi + Send + Sync, rc_strong};
+
+        let cargs: ChildArgs<AppDescriptor> = syn::parse2(input).unwrap();
+
+        assert_eq!(cargs.parent_ref_type().to_string(), "(dyn AppApi + Send + Sync)");
+        assert!(*cargs.rc_strong().is_set());
+    }
 }